@@ -1,6 +1,6 @@
 use std::mem;
 
-use casper_types::{ProtocolVersion, PublicKey, SecretKey};
+use casper_types::{BlockTime, ProtocolVersion, PublicKey, SecretKey};
 
 use super::deploy_item::DeployItem;
 use crate::shared::newtypes::Blake2bHash;
@@ -8,7 +8,7 @@ use crate::shared::newtypes::Blake2bHash;
 #[derive(Debug)]
 pub struct ExecuteRequest {
     pub parent_state_hash: Blake2bHash,
-    pub block_time: u64,
+    pub block_time: BlockTime,
     pub deploys: Vec<DeployItem>,
     pub protocol_version: ProtocolVersion,
     pub proposer: PublicKey,
@@ -17,7 +17,7 @@ pub struct ExecuteRequest {
 impl ExecuteRequest {
     pub fn new(
         parent_state_hash: Blake2bHash,
-        block_time: u64,
+        block_time: BlockTime,
         deploys: Vec<DeployItem>,
         protocol_version: ProtocolVersion,
         proposer: PublicKey,
@@ -47,7 +47,7 @@ impl Default for ExecuteRequest {
         let proposer = PublicKey::from(&proposer_secret_key);
         Self {
             parent_state_hash: Blake2bHash::new(&[]),
-            block_time: 0,
+            block_time: BlockTime::default(),
             deploys: vec![],
             protocol_version: Default::default(),
             proposer,
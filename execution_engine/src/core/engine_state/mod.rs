@@ -441,7 +441,7 @@ where
                     &executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
+                    exec_request.block_time,
                     deploy_item,
                     exec_request.proposer.clone(),
                 ),
@@ -450,7 +450,7 @@ where
                     &executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
+                    exec_request.block_time,
                     deploy_item,
                     exec_request.proposer.clone(),
                 ),
@@ -2001,7 +2001,7 @@ where
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(
                         ARG_ERA_END_TIMESTAMP_MILLIS,
-                        step_request.era_end_timestamp_millis,
+                        u64::from(step_request.era_end_timestamp_millis),
                     )?;
                     args.insert(
                         ARG_EVICTED_VALIDATORS,
@@ -4,7 +4,8 @@ use core::fmt;
 use uint::static_assertions::_core::fmt::Formatter;
 
 use casper_types::{
-    bytesrepr, bytesrepr::ToBytes, CLValueError, EraId, Key, ProtocolVersion, PublicKey, U512,
+    bytesrepr, bytesrepr::ToBytes, BlockTime, CLValueError, EraId, Key, ProtocolVersion, PublicKey,
+    U512,
 };
 
 use crate::{
@@ -38,6 +39,13 @@ impl RewardItem {
     }
 }
 
+/// The reason a validator is evicted from the validator set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictReason {
+    /// The validator didn't take part in consensus throughout the era.
+    Inactive,
+}
+
 #[derive(Debug)]
 pub struct EvictItem {
     pub validator_id: PublicKey,
@@ -49,6 +57,28 @@ impl EvictItem {
     }
 }
 
+/// An [`EvictItem`] along with the reason for the eviction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictItemWithReason {
+    pub validator_id: PublicKey,
+    pub reason: EvictReason,
+}
+
+impl EvictItemWithReason {
+    pub fn new(validator_id: PublicKey, reason: EvictReason) -> Self {
+        Self {
+            validator_id,
+            reason,
+        }
+    }
+}
+
+impl From<EvictItemWithReason> for EvictItem {
+    fn from(evict_item: EvictItemWithReason) -> Self {
+        EvictItem::new(evict_item.validator_id)
+    }
+}
+
 #[derive(Debug)]
 pub struct StepRequest {
     pub pre_state_hash: Blake2bHash,
@@ -58,7 +88,7 @@ pub struct StepRequest {
     pub evict_items: Vec<EvictItem>,
     pub run_auction: bool,
     pub next_era_id: EraId,
-    pub era_end_timestamp_millis: u64,
+    pub era_end_timestamp_millis: BlockTime,
 }
 
 impl StepRequest {
@@ -71,7 +101,7 @@ impl StepRequest {
         evict_items: Vec<EvictItem>,
         run_auction: bool,
         next_era_id: EraId,
-        era_end_timestamp_millis: u64,
+        era_end_timestamp_millis: BlockTime,
     ) -> Self {
         Self {
             pre_state_hash,
@@ -146,6 +146,34 @@ where
 }
 
 impl Transform {
+    /// Returns `true` if this is a [`Transform::Identity`], which leaves the value unchanged.
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Transform::Identity)
+    }
+
+    /// Returns `true` if this is a [`Transform::Write`] of any kind of value.
+    pub fn is_write(&self) -> bool {
+        matches!(self, Transform::Write(_))
+    }
+
+    /// Returns `true` if this is a [`Transform::Write`] of a [`CLValue`].
+    pub fn is_cl_value_write(&self) -> bool {
+        matches!(self, Transform::Write(StoredValue::CLValue(_)))
+    }
+
+    /// Returns `true` if this is a wrapping addition to an integer value, i.e. one of the
+    /// `Transform::AddInt32` to `Transform::AddUInt512` variants.
+    pub fn is_add_int(&self) -> bool {
+        matches!(
+            self,
+            Transform::AddInt32(_)
+                | Transform::AddUInt64(_)
+                | Transform::AddUInt128(_)
+                | Transform::AddUInt256(_)
+                | Transform::AddUInt512(_)
+        )
+    }
+
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -837,4 +865,57 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    #[test]
+    fn should_classify_transforms() {
+        let account = StoredValue::Account(Account::create(
+            ZERO_PUBLIC_KEY,
+            BTreeMap::new(),
+            URef::new(ZERO_ARRAY, AccessRights::READ_ADD_WRITE),
+        ));
+        let failure = TypeMismatch::new("expected".to_string(), "found".to_string());
+
+        // Each case is (transform, is_identity, is_write, is_cl_value_write, is_add_int).
+        let cases = vec![
+            (Transform::Identity, true, false, false, false),
+            (
+                Transform::Write(StoredValue::CLValue(CLValue::unit())),
+                false,
+                true,
+                true,
+                false,
+            ),
+            (Transform::Write(account), false, true, false, false),
+            (Transform::AddInt32(ONE_I32), false, false, false, true),
+            (Transform::AddUInt64(ONE_U64), false, false, false, true),
+            (Transform::AddUInt128(ONE_U128), false, false, false, true),
+            (Transform::AddUInt256(ONE_U256), false, false, false, true),
+            (Transform::AddUInt512(ONE_U512), false, false, false, true),
+            (
+                Transform::AddKeys(BTreeMap::new()),
+                false,
+                false,
+                false,
+                false,
+            ),
+            (
+                Transform::Failure(failure.into()),
+                false,
+                false,
+                false,
+                false,
+            ),
+        ];
+        for (transform, is_identity, is_write, is_cl_value_write, is_add_int) in cases {
+            assert_eq!(transform.is_identity(), is_identity, "{:?}", transform);
+            assert_eq!(transform.is_write(), is_write, "{:?}", transform);
+            assert_eq!(
+                transform.is_cl_value_write(),
+                is_cl_value_write,
+                "{:?}",
+                transform
+            );
+            assert_eq!(transform.is_add_int(), is_add_int, "{:?}", transform);
+        }
+    }
 }
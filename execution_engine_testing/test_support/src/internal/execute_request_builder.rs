@@ -6,8 +6,8 @@ use casper_execution_engine::core::engine_state::{
     deploy_item::DeployItem, execute_request::ExecuteRequest,
 };
 use casper_types::{
-    account::AccountHash, runtime_args, ContractHash, ContractPackageHash, ContractVersion,
-    ProtocolVersion, RuntimeArgs,
+    account::AccountHash, runtime_args, BlockTime, ContractHash, ContractPackageHash,
+    ContractVersion, ProtocolVersion, RuntimeArgs,
 };
 
 use crate::internal::{
@@ -41,7 +41,7 @@ impl ExecuteRequestBuilder {
     }
 
     pub fn with_block_time(mut self, block_time: u64) -> Self {
-        self.execute_request.block_time = block_time;
+        self.execute_request.block_time = BlockTime::new(block_time);
         self
     }
 
@@ -187,7 +187,7 @@ impl ExecuteRequestBuilder {
 impl Default for ExecuteRequestBuilder {
     fn default() -> Self {
         let execute_request = ExecuteRequest {
-            block_time: DEFAULT_BLOCK_TIME,
+            block_time: BlockTime::new(DEFAULT_BLOCK_TIME),
             protocol_version: ProtocolVersion::V1_0_0,
             proposer: DEFAULT_PROPOSER_PUBLIC_KEY.clone(),
             ..Default::default()
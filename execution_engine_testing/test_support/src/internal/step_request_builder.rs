@@ -5,7 +5,7 @@ use casper_execution_engine::{
     },
     shared::newtypes::Blake2bHash,
 };
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{BlockTime, EraId, ProtocolVersion};
 
 #[derive(Debug)]
 pub struct StepRequestBuilder {
@@ -73,7 +73,7 @@ impl StepRequestBuilder {
             self.evict_items,
             self.run_auction,
             self.next_era_id,
-            self.era_end_timestamp_millis,
+            BlockTime::new(self.era_end_timestamp_millis),
         )
     }
 }
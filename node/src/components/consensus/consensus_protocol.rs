@@ -109,6 +109,20 @@ pub struct EraReport<VID> {
     pub(crate) inactive_validators: Vec<VID>,
 }
 
+impl<VID: Ord> EraReport<VID> {
+    /// Returns the sum of all validators' rewards, saturating at `u64::MAX`.
+    pub fn total_rewards(&self) -> u64 {
+        self.rewards
+            .values()
+            .fold(0, |total, reward| total.saturating_add(*reward))
+    }
+
+    /// Returns the reward for the given validator, or `None` if it has no reward entry.
+    pub fn reward_for(&self, validator_id: &VID) -> Option<u64> {
+        self.rewards.get(validator_id).copied()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct TerminalBlockData<C: Context> {
     /// The rewards for participating in consensus.
@@ -256,3 +270,33 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
     // TODO: Make this less Highway-specific.
     fn next_round_length(&self) -> Option<TimeDiff>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn era_report_should_sum_and_look_up_rewards() {
+        let era_report = EraReport {
+            equivocators: vec![3],
+            rewards: vec![(1u32, 1000), (2, 250)].into_iter().collect(),
+            inactive_validators: vec![],
+        };
+
+        assert_eq!(era_report.total_rewards(), 1250);
+        assert_eq!(era_report.reward_for(&1), Some(1000));
+        assert_eq!(era_report.reward_for(&2), Some(250));
+        assert_eq!(era_report.reward_for(&3), None);
+    }
+
+    #[test]
+    fn era_report_total_rewards_should_saturate() {
+        let era_report = EraReport {
+            equivocators: vec![],
+            rewards: vec![(1u32, u64::MAX), (2, 1)].into_iter().collect(),
+            inactive_validators: vec![],
+        };
+
+        assert_eq!(era_report.total_rewards(), u64::MAX);
+    }
+}
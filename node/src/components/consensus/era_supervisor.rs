@@ -1111,7 +1111,7 @@ where
                     .era_supervisor
                     .next_block_height
                     .max(finalized_block.height() + 1);
-                if finalized_block.era_report().is_some() {
+                if finalized_block.is_switch_block() {
                     // This was the era's last block. Schedule deactivating this era.
                     let delay = Timestamp::now().saturating_diff(timestamp).into();
                     let faulty_num = era.consensus.validators_with_evidence().len();
@@ -1,26 +1,33 @@
 //! Contract Runtime component.
 mod config;
 mod operations;
+#[cfg(test)]
+mod tests;
 mod types;
 
 use std::{
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
     fmt::{self, Debug, Formatter},
     sync::Arc,
     time::Instant,
 };
 
 pub use config::Config;
+pub use operations::{apply_transforms, results_to_map};
 use smallvec::SmallVec;
 
 pub use types::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest};
 
 use datasize::DataSize;
 use derive_more::From;
+use hex_fmt::HexFmt;
 use lmdb::DatabaseFlags;
-use prometheus::{self, Histogram, HistogramOpts, IntGauge, Registry};
+use prometheus::{
+    self, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts,
+    Registry,
+};
 use thiserror::Error;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
     core::engine_state::{
@@ -36,7 +43,8 @@ use casper_execution_engine::{
     },
 };
 use casper_types::{
-    system::auction::ValidatorWeights, ExecutionResult, ProtocolVersion, PublicKey, U512,
+    system::auction::ValidatorWeights, BlockTime, ExecutionResult, Key, ProtocolVersion, PublicKey,
+    U512,
 };
 
 use crate::{
@@ -143,6 +151,12 @@ pub struct ContractRuntime {
 
     protocol_version: ProtocolVersion,
 
+    /// The maximum number of attempts made to commit execution effects.
+    max_commit_attempts: u32,
+
+    /// Whether successful deploy executions are logged at trace rather than debug level.
+    demote_deploy_success_logs: bool,
+
     /// A mapping from block height to executed block's ID and post-state hash, to allow
     /// identification of a parent block's details once a finalized block has been executed.
     ///
@@ -164,6 +178,8 @@ impl Debug for ContractRuntime {
 #[derive(Debug)]
 pub struct ContractRuntimeMetrics {
     run_execute: Histogram,
+    /// Time taken by engine_state.run_execute, labeled by deploy category.
+    run_execute_by_category: HistogramVec,
     apply_effect: Histogram,
     commit_upgrade: Histogram,
     run_query: Histogram,
@@ -176,6 +192,18 @@ pub struct ContractRuntimeMetrics {
     missing_trie_keys: Histogram,
     put_trie: Histogram,
     read_trie: Histogram,
+    /// Number of times committing execution effects was retried after a transient failure.
+    commit_retries: IntCounter,
+    /// Number of deploys which executed successfully.
+    deploy_success_total: IntCounter,
+    /// Number of deploys which failed to execute.
+    deploy_failure_total: IntCounter,
+    /// Number of transforms committed, labeled by transform type.
+    transforms_committed: IntCounterVec,
+    /// Number of executed blocks whose post-state root hash differs from their pre-state one.
+    state_root_advances_total: IntCounter,
+    /// Number of executed blocks whose post-state root hash equals their pre-state one.
+    state_root_unchanged_total: IntCounter,
     /// The current chain height.
     pub chain_height: IntGauge,
 }
@@ -194,6 +222,9 @@ const EXPONENTIAL_BUCKET_COUNT: usize = 10;
 
 const RUN_EXECUTE_NAME: &str = "contract_runtime_run_execute";
 const RUN_EXECUTE_HELP: &str = "tracking run of engine_state.run_execute in seconds.";
+const RUN_EXECUTE_BY_CATEGORY_NAME: &str = "contract_runtime_run_execute_by_category";
+const RUN_EXECUTE_BY_CATEGORY_HELP: &str =
+    "tracking run of engine_state.run_execute in seconds, by deploy category.";
 const APPLY_EFFECT_NAME: &str = "contract_runtime_apply_commit";
 const APPLY_EFFECT_HELP: &str = "tracking run of engine_state.apply_effect in seconds.";
 const RUN_QUERY_NAME: &str = "contract_runtime_run_query";
@@ -221,32 +252,89 @@ const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "tracking run of engine_state.put_trie in seconds.";
 const MISSING_TRIE_KEYS_NAME: &str = "contract_runtime_missing_trie_keys";
 const MISSING_TRIE_KEYS_HELP: &str = "tracking run of engine_state.missing_trie_keys in seconds.";
+const COMMIT_RETRIES_NAME: &str = "contract_runtime_commit_retries";
+const COMMIT_RETRIES_HELP: &str =
+    "number of times engine_state.apply_effect was retried after a transient failure.";
+const DEPLOY_SUCCESS_TOTAL_NAME: &str = "contract_runtime_deploy_success_total";
+const DEPLOY_SUCCESS_TOTAL_HELP: &str = "number of deploys which executed successfully.";
+const DEPLOY_FAILURE_TOTAL_NAME: &str = "contract_runtime_deploy_failure_total";
+const DEPLOY_FAILURE_TOTAL_HELP: &str = "number of deploys which failed to execute.";
+const TRANSFORMS_COMMITTED_NAME: &str = "contract_runtime_transforms_committed";
+const TRANSFORMS_COMMITTED_HELP: &str = "number of transforms committed, by transform type.";
+const STATE_ROOT_ADVANCES_TOTAL_NAME: &str = "contract_runtime_state_root_advances_total";
+const STATE_ROOT_ADVANCES_TOTAL_HELP: &str =
+    "number of executed blocks which changed the global state root hash.";
+const STATE_ROOT_UNCHANGED_TOTAL_NAME: &str = "contract_runtime_state_root_unchanged_total";
+const STATE_ROOT_UNCHANGED_TOTAL_HELP: &str =
+    "number of executed blocks which left the global state root hash unchanged.";
 
-/// Create prometheus Histogram and register.
-fn register_histogram_metric(
-    registry: &Registry,
+/// Create prometheus HistogramOpts with the buckets common to all contract runtime histograms.
+fn common_histogram_opts(
     metric_name: &str,
     metric_help: &str,
-) -> Result<Histogram, prometheus::Error> {
+) -> Result<HistogramOpts, prometheus::Error> {
     let common_buckets = prometheus::exponential_buckets(
         EXPONENTIAL_BUCKET_START,
         EXPONENTIAL_BUCKET_FACTOR,
         EXPONENTIAL_BUCKET_COUNT,
     )?;
-    let histogram_opts = HistogramOpts::new(metric_name, metric_help).buckets(common_buckets);
-    let histogram = Histogram::with_opts(histogram_opts)?;
+    Ok(HistogramOpts::new(metric_name, metric_help).buckets(common_buckets))
+}
+
+/// Create prometheus Histogram and register.
+fn register_histogram_metric(
+    registry: &Registry,
+    metric_name: &str,
+    metric_help: &str,
+) -> Result<Histogram, prometheus::Error> {
+    let histogram = Histogram::with_opts(common_histogram_opts(metric_name, metric_help)?)?;
     registry.register(Box::new(histogram.clone()))?;
     Ok(histogram)
 }
 
 impl ContractRuntimeMetrics {
     /// Constructor of metrics which creates and registers metrics objects for use.
-    fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let chain_height = IntGauge::new("chain_height", "current chain height")?;
         registry.register(Box::new(chain_height.clone()))?;
+        let commit_retries = IntCounter::new(COMMIT_RETRIES_NAME, COMMIT_RETRIES_HELP)?;
+        registry.register(Box::new(commit_retries.clone()))?;
+        let deploy_success_total =
+            IntCounter::new(DEPLOY_SUCCESS_TOTAL_NAME, DEPLOY_SUCCESS_TOTAL_HELP)?;
+        registry.register(Box::new(deploy_success_total.clone()))?;
+        let deploy_failure_total =
+            IntCounter::new(DEPLOY_FAILURE_TOTAL_NAME, DEPLOY_FAILURE_TOTAL_HELP)?;
+        registry.register(Box::new(deploy_failure_total.clone()))?;
+        let transforms_committed = IntCounterVec::new(
+            Opts::new(TRANSFORMS_COMMITTED_NAME, TRANSFORMS_COMMITTED_HELP),
+            &["transform"],
+        )?;
+        registry.register(Box::new(transforms_committed.clone()))?;
+        let state_root_advances_total = IntCounter::new(
+            STATE_ROOT_ADVANCES_TOTAL_NAME,
+            STATE_ROOT_ADVANCES_TOTAL_HELP,
+        )?;
+        registry.register(Box::new(state_root_advances_total.clone()))?;
+        let state_root_unchanged_total = IntCounter::new(
+            STATE_ROOT_UNCHANGED_TOTAL_NAME,
+            STATE_ROOT_UNCHANGED_TOTAL_HELP,
+        )?;
+        registry.register(Box::new(state_root_unchanged_total.clone()))?;
+        let run_execute_by_category = HistogramVec::new(
+            common_histogram_opts(RUN_EXECUTE_BY_CATEGORY_NAME, RUN_EXECUTE_BY_CATEGORY_HELP)?,
+            &["category"],
+        )?;
+        registry.register(Box::new(run_execute_by_category.clone()))?;
         Ok(ContractRuntimeMetrics {
             chain_height,
+            commit_retries,
+            deploy_success_total,
+            deploy_failure_total,
+            transforms_committed,
+            state_root_advances_total,
+            state_root_unchanged_total,
             run_execute: register_histogram_metric(registry, RUN_EXECUTE_NAME, RUN_EXECUTE_HELP)?,
+            run_execute_by_category,
             apply_effect: register_histogram_metric(
                 registry,
                 APPLY_EFFECT_NAME,
@@ -462,14 +550,14 @@ where
                         .ignore()
                     }
                     ContractRuntimeRequest::Step {
+                        correlation_id,
                         step_request,
                         responder,
                     } => {
-                        trace!(?step_request, "step request");
+                        trace!(?correlation_id, ?step_request, "step request");
                         let engine_state = Arc::clone(&self.engine_state);
                         let metrics = Arc::clone(&self.metrics);
                         async move {
-                            let correlation_id = CorrelationId::new();
                             let start = Instant::now();
                             let result = engine_state.commit_step(correlation_id, step_request);
                             metrics.commit_step.observe(start.elapsed().as_secs_f64());
@@ -606,7 +694,7 @@ where
                     )
                 }
                 ContractRuntimeResult::RunStepResult { mut state, result } => {
-                    trace!(?result, "run step result");
+                    trace!(era_id = %state.finalized_block.era_id(), ?result, "run step result");
                     match result {
                         Ok(StepResult::Success {
                             post_state_hash,
@@ -615,6 +703,20 @@ where
                         }) => {
                             state.state_root_hash = post_state_hash.into();
                             let era_id = state.finalized_block.era_id();
+                            let evictions = state.finalized_block.era_report().map_or_else(
+                                Vec::new,
+                                |era_report| {
+                                    operations::evict_items(&era_report.inactive_validators)
+                                },
+                            );
+                            for eviction in evictions {
+                                info!(
+                                    %era_id,
+                                    validator_id = %eviction.validator_id,
+                                    reason = ?eviction.reason,
+                                    "validator evicted"
+                                );
+                            }
                             let mut effects = effect_builder
                                 .announce_step_success(era_id, execution_effect)
                                 .ignore();
@@ -627,7 +729,18 @@ where
                         }
                         _ => {
                             // When step fails, the auction process is broken and we should panic.
-                            error!(?result, "run step failed - internal contract runtime error");
+                            // The EE doesn't report how far the step progressed, so log the sizes
+                            // of its inputs to help operators correlate the failure.
+                            let counts =
+                                operations::step_item_counts(state.finalized_block.era_report());
+                            error!(
+                                ?result,
+                                era_id = %state.finalized_block.era_id(),
+                                reward_items = %counts.reward_items,
+                                slash_items = %counts.slash_items,
+                                evict_items = %counts.evict_items,
+                                "run step failed - internal contract runtime error"
+                            );
                             panic!("unable to run step");
                         }
                     }
@@ -649,6 +762,36 @@ pub enum ConfigError {
     /// Error initializing metrics.
     #[error("failed to initialize metrics for contract runtime: {0}")]
     Prometheus(#[from] prometheus::Error),
+    /// Error reading the protocol data from global state.
+    #[error("failed to read protocol data from global state: {0}")]
+    ProtocolData(#[from] engine_state::Error),
+    /// Global state was not written under the configured protocol version.
+    #[error(
+        "global state was not written under configured protocol version {expected}, protocol \
+        version of the highest known block is {actual}"
+    )]
+    ProtocolVersionMismatch {
+        /// The protocol version the node is configured to run.
+        expected: ProtocolVersion,
+        /// The protocol version of the highest known block.
+        actual: ProtocolVersion,
+    },
+}
+
+/// Error returned while executing the deploys of a finalized block.
+#[derive(Debug, Error)]
+pub enum BlockExecutionError {
+    /// The number of execution results doesn't match the number of deploys executed.
+    #[error(
+        "expected {deploy_hashes} execution results, but the execution engine returned \
+        {execution_results}"
+    )]
+    ExecutionResultsMismatch {
+        /// The number of deploys executed.
+        deploy_hashes: usize,
+        /// The number of execution results returned.
+        execution_results: usize,
+    },
 }
 
 impl ContractRuntime {
@@ -689,6 +832,8 @@ impl ContractRuntime {
         Ok(ContractRuntime {
             initial_state,
             protocol_version,
+            max_commit_attempts: contract_runtime_config.max_commit_attempts(),
+            demote_deploy_success_logs: contract_runtime_config.demote_deploy_success_logs(),
             parent_map: HashMap::new(),
             exec_queue: HashMap::new(),
             engine_state,
@@ -726,12 +871,23 @@ impl ContractRuntime {
         }
     }
 
+    /// Sets the state the first block is executed on.
+    ///
+    /// Returns an error if global state was not written under the configured protocol version.
     pub(crate) fn set_initial_state(
         &mut self,
         initial_state_root_hash: Digest,
         initial_block_header: Option<&BlockHeader>,
-    ) {
+    ) -> Result<(), ConfigError> {
+        if let Some(block_header) = initial_block_header {
+            operations::check_protocol_version(
+                &self.engine_state,
+                block_header,
+                self.protocol_version,
+            )?;
+        }
         self.initial_state = InitialState::new(initial_state_root_hash, initial_block_header);
+        Ok(())
     }
 
     /// Adds the "parent map" to the instance of `ContractRuntime`.
@@ -819,6 +975,11 @@ impl ContractRuntime {
         self.metrics
             .chain_height
             .set(state.finalized_block.height() as i64);
+        operations::record_state_root_change(
+            &self.metrics,
+            state.pre_state_root_hash,
+            state.state_root_hash,
+        );
         let block = self.create_block(
             state.finalized_block,
             state.state_root_hash,
@@ -826,7 +987,12 @@ impl ContractRuntime {
         );
 
         let mut effects = effect_builder
-            .announce_linear_chain_block(block, state.execution_results)
+            .announce_linear_chain_block(
+                block,
+                state.execution_results,
+                state.transform_counts,
+                state.touched_keys,
+            )
             .ignore();
         // If the child is already finalized, start execution.
         if let Some((finalized_block, deploys)) = self.exec_queue.remove(&next_height) {
@@ -839,6 +1005,28 @@ impl ContractRuntime {
         effects
     }
 
+    /// Starts executing the `deploys` of `finalized_block` on top of `pre_state_root_hash`.
+    fn start_block_execution<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        finalized_block: FinalizedBlock,
+        deploys: VecDeque<Deploy>,
+        pre_state_root_hash: Digest,
+    ) -> Effects<Event> {
+        debug!(
+            era_id = %finalized_block.era_id(),
+            height = %finalized_block.height(),
+            deploys = %deploys.len(),
+            "executing block"
+        );
+        let state = Box::new(RequestState::new(
+            finalized_block,
+            deploys,
+            pre_state_root_hash,
+        ));
+        self.execute_all_deploys_or_finalize_block_or_step(effect_builder, state)
+    }
+
     fn execute_all_deploys_or_finalize_block_or_step<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -866,18 +1054,22 @@ impl ContractRuntime {
             .rewards
             .iter()
             .map(|(vid, &value)| RewardItem::new(vid.clone(), value))
-            .collect();
+            .collect::<Vec<_>>();
+        debug!(
+            era_id = %state.finalized_block.era_id(),
+            total_rewards = %operations::total_rewards(&reward_items),
+            "running step"
+        );
         let slash_items = era_end
             .equivocators
             .iter()
             .map(|vid| SlashItem::new(vid.clone()))
             .collect();
-        let evict_items = era_end
-            .inactive_validators
-            .iter()
-            .map(|vid| EvictItem::new(vid.clone()))
+        let evict_items = operations::evict_items(&era_end.inactive_validators)
+            .into_iter()
+            .map(EvictItem::from)
             .collect();
-        let era_end_timestamp_millis = state.finalized_block.timestamp().millis();
+        let era_end_timestamp_millis = BlockTime::from(state.finalized_block.timestamp());
         let request = StepRequest {
             pre_state_hash: state.state_root_hash.into(),
             protocol_version: self.protocol_version,
@@ -888,20 +1080,25 @@ impl ContractRuntime {
             next_era_id: state.finalized_block.era_id().successor(),
             era_end_timestamp_millis,
         };
-        effect_builder.run_step(request).event(|result| {
-            Event::Result(Box::new(ContractRuntimeResult::RunStepResult {
-                state,
-                result,
-            }))
-        })
+        effect_builder
+            .run_step(state.correlation_id, request)
+            .event(|result| {
+                Event::Result(Box::new(ContractRuntimeResult::RunStepResult {
+                    state,
+                    result,
+                }))
+            })
     }
 
     fn execute_all_deploys_in_block(&mut self, mut state: Box<RequestState>) -> Effects<Event> {
         let engine_state = Arc::clone(&self.engine_state);
         let metrics = Arc::clone(&self.metrics);
         let protocol_version = self.protocol_version;
-        let block_time = state.finalized_block.timestamp().millis();
+        let max_commit_attempts = self.max_commit_attempts;
+        let demote_deploy_success_logs = self.demote_deploy_success_logs;
+        let block_time = BlockTime::from(state.finalized_block.timestamp());
         let proposer = state.finalized_block.proposer();
+        let era_id = state.finalized_block.era_id();
         async move {
             for deploy in state.remaining_deploys.drain(..) {
                 let deploy_hash = *deploy.id();
@@ -921,19 +1118,33 @@ impl ContractRuntime {
                 // mapping between deploy_hash and execution result, and this outer logic is
                 // enriching it with the deploy hash. If we were passing multiple deploys per exec
                 // the relation between the deploy and the execution results would be lost.
-                let result =
-                    operations::execute(engine_state.clone(), metrics.clone(), execute_request)
-                        .await;
+                let result = operations::execute(
+                    engine_state.clone(),
+                    metrics.clone(),
+                    state.correlation_id,
+                    execute_request,
+                )
+                .await;
 
-                trace!(%deploy_hash, ?result, "deploy execution result");
+                trace!(
+                    %era_id,
+                    deploy_hash = %HexFmt(deploy_hash.inner()),
+                    ?result,
+                    "deploy execution result"
+                );
                 // As for now a given state is expected to exist.
                 let execution_results = result.unwrap();
                 match operations::commit_execution_effects(
                     engine_state.clone(),
                     metrics.clone(),
+                    state.correlation_id,
                     state.state_root_hash,
                     deploy_hash,
                     execution_results,
+                    max_commit_attempts,
+                    demote_deploy_success_logs,
+                    Some(&mut state.transform_counts),
+                    Some(&mut state.touched_keys),
                 )
                 .await
                 {
@@ -960,13 +1171,7 @@ impl ContractRuntime {
         deploys: VecDeque<Deploy>,
     ) -> Effects<Event> {
         if let Some(state_root_hash) = self.pre_state_hash(&finalized_block) {
-            let state = Box::new(RequestState {
-                finalized_block,
-                remaining_deploys: deploys,
-                execution_results: HashMap::new(),
-                state_root_hash,
-            });
-            self.execute_all_deploys_or_finalize_block_or_step(effect_builder, state)
+            self.start_block_execution(effect_builder, finalized_block, deploys, state_root_hash)
         } else {
             // Didn't find parent in the `parent_map` cache.
             // Read it from the storage.
@@ -1002,13 +1207,12 @@ impl ContractRuntime {
                 debug!("no pre-state hash for height {}", height);
                 // re-check the parent map - the parent might have been executed in the meantime!
                 if let Some(state_root_hash) = self.pre_state_hash(&finalized_block) {
-                    let state = Box::new(RequestState {
+                    self.start_block_execution(
+                        effect_builder,
                         finalized_block,
-                        remaining_deploys: deploys,
-                        execution_results: HashMap::new(),
+                        deploys,
                         state_root_hash,
-                    });
-                    self.execute_all_deploys_or_finalize_block_or_step(effect_builder, state)
+                    )
                 } else {
                     // The parent block has not been executed yet; delay handling.
                     self.exec_queue.insert(height, (finalized_block, deploys));
@@ -1100,11 +1304,38 @@ pub struct RequestState {
     pub finalized_block: FinalizedBlock,
     /// Deploys which have still to be executed.
     pub remaining_deploys: VecDeque<Deploy>,
-    /// A collection of results of executing the deploys.
-    pub execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    /// A collection of results of executing the deploys, ordered by deploy hash.
+    pub execution_results: BTreeMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    /// The number of transforms committed for each executed deploy, in execution order.
+    pub transform_counts: Vec<(DeployHash, usize)>,
+    /// The keys of all transforms committed for the deploys executed so far.
+    pub touched_keys: BTreeSet<Key>,
+    /// The parent block's state root hash, on top of which this block is executed.
+    pub pre_state_root_hash: Digest,
     /// Current state root hash of global storage.  Is initialized with the parent block's
     /// state hash, and is updated after each commit.
     pub state_root_hash: Digest,
+    /// The correlation id shared by all execution engine operations run for this block.
+    pub correlation_id: CorrelationId,
+}
+
+impl RequestState {
+    fn new(
+        finalized_block: FinalizedBlock,
+        remaining_deploys: VecDeque<Deploy>,
+        state_root_hash: Digest,
+    ) -> Self {
+        RequestState {
+            finalized_block,
+            remaining_deploys,
+            execution_results: BTreeMap::new(),
+            transform_counts: Vec::new(),
+            touched_keys: BTreeSet::new(),
+            pre_state_root_hash: state_root_hash,
+            state_root_hash,
+            correlation_id: CorrelationId::new(),
+        }
+    }
 }
 
 #[derive(DataSize, Debug, Default)]
@@ -6,6 +6,8 @@ use casper_execution_engine::shared::utils;
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+const DEFAULT_MAX_COMMIT_ATTEMPTS: u32 = 3;
+const DEFAULT_DEMOTE_DEPLOY_SUCCESS_LOGS: bool = true;
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -26,6 +28,16 @@ pub struct Config {
     ///
     /// Defaults to 5.
     max_query_depth: Option<u64>,
+    /// The maximum number of attempts made to commit execution effects when the global state
+    /// store fails with a transient error.
+    ///
+    /// Defaults to 3.
+    max_commit_attempts: Option<u32>,
+    /// Whether successful deploy executions and their commits are logged at trace rather than
+    /// debug level, so that failures stand out on busy nodes.
+    ///
+    /// Defaults to true.
+    demote_deploy_success_logs: Option<bool>,
 }
 
 impl Config {
@@ -44,6 +56,17 @@ impl Config {
     pub(crate) fn max_query_depth(&self) -> u64 {
         self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH)
     }
+
+    pub(crate) fn max_commit_attempts(&self) -> u32 {
+        self.max_commit_attempts
+            .unwrap_or(DEFAULT_MAX_COMMIT_ATTEMPTS)
+            .max(1)
+    }
+
+    pub(crate) fn demote_deploy_success_logs(&self) -> bool {
+        self.demote_deploy_success_logs
+            .unwrap_or(DEFAULT_DEMOTE_DEPLOY_SUCCESS_LOGS)
+    }
 }
 
 impl Default for Config {
@@ -52,6 +75,8 @@ impl Default for Config {
             max_global_state_size: Some(DEFAULT_MAX_GLOBAL_STATE_SIZE),
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
+            max_commit_attempts: Some(DEFAULT_MAX_COMMIT_ATTEMPTS),
+            demote_deploy_success_logs: Some(DEFAULT_DEMOTE_DEPLOY_SUCCESS_LOGS),
         }
     }
 }
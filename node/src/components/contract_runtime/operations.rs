@@ -1,26 +1,59 @@
-use std::{collections::VecDeque, sync::Arc, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use super::ContractRuntimeMetrics;
-use crate::{crypto::hash::Digest, types::DeployHash};
+use super::{BlockExecutionError, ConfigError, ContractRuntimeMetrics};
+use crate::{
+    components::consensus::EraReport,
+    crypto::hash::Digest,
+    types::{BlockHeader, DeployHash},
+};
 use casper_execution_engine::{
-    core::engine_state::{
-        self, EngineState, ExecutionResult as EngineExecutionResult, ExecutionResults,
+    core::{
+        engine_state::{
+            self,
+            step::{EvictItemWithReason, EvictReason},
+            EngineState, ExecutionResult as EngineExecutionResult, ExecutionResults, RewardItem,
+        },
+        execution,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
-    storage::global_state::{lmdb::LmdbGlobalState, CommitResult},
+    storage::{
+        self,
+        global_state::{lmdb::LmdbGlobalState, CommitResult},
+    },
 };
-use casper_types::{ExecutionResult, Key};
+use casper_types::{ExecutionResult, Key, ProtocolVersion, PublicKey, U512};
 use engine_state::ExecuteRequest;
+use hex_fmt::HexFmt;
 use itertools::Itertools;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
+
+/// Base delay between attempts to commit, multiplied by the number of the failed attempt.
+const COMMIT_RETRY_BACKOFF: Duration = Duration::from_millis(10);
 
 /// Commits the execution effects.
+///
+/// Successful executions and their commits are logged at trace level if `demote_success_logs` is
+/// set, otherwise at debug level like failed executions.
+///
+/// If `transform_counts` is provided, the deploy hash and the number of transforms committed for
+/// it are appended to it once the commit succeeds. Likewise, if `touched_keys` is provided, the
+/// keys of all committed transforms are added to it.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn commit_execution_effects(
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
+    correlation_id: CorrelationId,
     state_root_hash: Digest,
     deploy_hash: DeployHash,
     execution_results: ExecutionResults,
+    max_commit_attempts: u32,
+    demote_success_logs: bool,
+    transform_counts: Option<&mut Vec<(DeployHash, usize)>>,
+    touched_keys: Option<&mut BTreeSet<Key>>,
 ) -> Result<(Digest, ExecutionResult), ()> {
     let ee_execution_result = execution_results
         .into_iter()
@@ -30,9 +63,15 @@ pub(super) async fn commit_execution_effects(
 
     let execution_effect = match ee_execution_result {
         EngineExecutionResult::Success { effect, cost, .. } => {
-            // We do want to see the deploy hash and cost in the logs.
+            // We do want to see the deploy hash and cost in the logs, though possibly only at trace
+            // level so that busy nodes don't drown failures in successes.
             // We don't need to see the effects in the logs.
-            debug!(?deploy_hash, %cost, "execution succeeded");
+            if demote_success_logs {
+                trace!(deploy_hash = %HexFmt(deploy_hash.inner()), %cost, "execution succeeded");
+            } else {
+                debug!(deploy_hash = %HexFmt(deploy_hash.inner()), %cost, "execution succeeded");
+            }
+            metrics.deploy_success_total.inc();
             effect
         }
         EngineExecutionResult::Failure {
@@ -44,21 +83,44 @@ pub(super) async fn commit_execution_effects(
             // Failure to execute a contract is a user error, not a system error.
             // We do want to see the deploy hash, error, and cost in the logs.
             // We don't need to see the effects in the logs.
-            debug!(?deploy_hash, ?error, %cost, "execution failure");
+            debug!(
+                deploy_hash = %HexFmt(deploy_hash.inner()),
+                ?error,
+                %cost,
+                "execution failure"
+            );
+            metrics.deploy_failure_total.inc();
             effect
         }
     };
+    let transform_count = execution_effect.transforms.len();
+    let transform_keys: Vec<Key> = match touched_keys {
+        Some(_) => execution_effect.transforms.keys().copied().collect(),
+        None => Vec::new(),
+    };
     let commit_result = commit(
         engine_state,
         metrics,
+        correlation_id,
         state_root_hash,
         execution_effect.transforms,
+        max_commit_attempts,
     )
     .await;
     trace!(?commit_result, "commit result");
     match commit_result {
         Ok(CommitResult::Success { state_root }) => {
-            debug!(?state_root, "commit succeeded");
+            if demote_success_logs {
+                trace!(?state_root, "commit succeeded");
+            } else {
+                debug!(?state_root, "commit succeeded");
+            }
+            if let Some(transform_counts) = transform_counts {
+                transform_counts.push((deploy_hash, transform_count));
+            }
+            if let Some(touched_keys) = touched_keys {
+                touched_keys.extend(transform_keys);
+            }
             Ok((state_root.into(), execution_result))
         }
         _ => {
@@ -71,31 +133,864 @@ pub(super) async fn commit_execution_effects(
     }
 }
 
+/// Returns the sum of the values of the `reward_items`.
+///
+/// The values are added up as `U512`, so the sum cannot overflow.
+pub(super) fn total_rewards(reward_items: &[RewardItem]) -> U512 {
+    reward_items
+        .iter()
+        .fold(U512::zero(), |total, reward_item| {
+            total + U512::from(reward_item.value)
+        })
+}
+
+/// The number of items of each kind a step is run with.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct StepItemCounts {
+    pub(super) reward_items: usize,
+    pub(super) slash_items: usize,
+    pub(super) evict_items: usize,
+}
+
+/// Returns the number of items the step for a block with the given `era_report` is run with.
+pub(super) fn step_item_counts(era_report: Option<&EraReport<PublicKey>>) -> StepItemCounts {
+    era_report.map_or_else(StepItemCounts::default, |era_report| StepItemCounts {
+        reward_items: era_report.rewards.len(),
+        slash_items: era_report.equivocators.len(),
+        evict_items: era_report.inactive_validators.len(),
+    })
+}
+
+/// Records whether executing a block moved the global state root hash from
+/// `pre_state_root_hash` to a different `post_state_root_hash`.
+pub(super) fn record_state_root_change(
+    metrics: &ContractRuntimeMetrics,
+    pre_state_root_hash: Digest,
+    post_state_root_hash: Digest,
+) {
+    if post_state_root_hash == pre_state_root_hash {
+        metrics.state_root_unchanged_total.inc();
+    } else {
+        metrics.state_root_advances_total.inc();
+    }
+}
+
+/// Returns the category under which the execution of `execute_request` is recorded: "transfer"
+/// if it holds only native transfers, otherwise "contract".
+fn deploy_category(execute_request: &ExecuteRequest) -> &'static str {
+    let deploys = execute_request.deploys();
+    if !deploys.is_empty()
+        && deploys
+            .iter()
+            .all(|deploy_item| deploy_item.session.is_transfer())
+    {
+        "transfer"
+    } else {
+        "contract"
+    }
+}
+
+/// Returns the items evicting the `inactive_validators` from the validator set, along with the
+/// reason for each eviction.
+pub(super) fn evict_items(inactive_validators: &[PublicKey]) -> Vec<EvictItemWithReason> {
+    inactive_validators
+        .iter()
+        .map(|validator_id| EvictItemWithReason::new(validator_id.clone(), EvictReason::Inactive))
+        .collect()
+}
+
+/// Checks that global state was written under the configured `protocol_version`, given the
+/// `highest_block_header` known to the node.
+///
+/// The configured version must not be older than that of the highest block, and global state
+/// must hold the protocol data of the configured version, i.e. genesis or the upgrade to it must
+/// already have been committed.
+pub(super) fn check_protocol_version(
+    engine_state: &EngineState<LmdbGlobalState>,
+    highest_block_header: &BlockHeader,
+    protocol_version: ProtocolVersion,
+) -> Result<(), ConfigError> {
+    let actual = highest_block_header.protocol_version();
+    if actual > protocol_version || engine_state.get_protocol_data(protocol_version)?.is_none() {
+        return Err(ConfigError::ProtocolVersionMismatch {
+            expected: protocol_version,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Pairs each of the `deploy_hashes` with the execution result at the same position in
+/// `execution_results`.
+///
+/// Returns an error if the number of execution results differs from the number of deploy hashes.
+pub fn results_to_map(
+    deploy_hashes: &[DeployHash],
+    execution_results: ExecutionResults,
+) -> Result<HashMap<DeployHash, EngineExecutionResult>, BlockExecutionError> {
+    if deploy_hashes.len() != execution_results.len() {
+        return Err(BlockExecutionError::ExecutionResultsMismatch {
+            deploy_hashes: deploy_hashes.len(),
+            execution_results: execution_results.len(),
+        });
+    }
+    Ok(deploy_hashes
+        .iter()
+        .copied()
+        .zip(execution_results)
+        .collect())
+}
+
+/// Applies externally computed `effects` to global state on top of `state_root_hash`, returning
+/// the new state root hash.
+///
+/// The effects are committed via the same measured path used for deploy execution, making up to
+/// `max_commit_attempts` attempts in the face of transient failures.
+pub async fn apply_transforms(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    state_root_hash: Digest,
+    effects: AdditiveMap<Key, Transform>,
+    max_commit_attempts: u32,
+) -> Result<Digest, engine_state::Error> {
+    match commit(
+        engine_state,
+        metrics,
+        CorrelationId::new(),
+        state_root_hash,
+        effects,
+        max_commit_attempts,
+    )
+    .await?
+    {
+        CommitResult::Success { state_root } => Ok(state_root.into()),
+        CommitResult::RootNotFound => {
+            Err(engine_state::Error::RootNotFound(state_root_hash.into()))
+        }
+        CommitResult::KeyNotFound(key) => Err(execution::Error::KeyNotFound(key).into()),
+        CommitResult::TypeMismatch(type_mismatch) => {
+            Err(execution::Error::TypeMismatch(type_mismatch).into())
+        }
+        CommitResult::Serialization(error) => Err(error.into()),
+    }
+}
+
+/// Commits `effects` on top of `state_root_hash`, retrying up to `max_commit_attempts` times in
+/// total if the global state store fails with a transient error.
 pub(super) async fn commit(
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
+    correlation_id: CorrelationId,
     state_root_hash: Digest,
     effects: AdditiveMap<Key, Transform>,
+    max_commit_attempts: u32,
 ) -> Result<CommitResult, engine_state::Error> {
-    trace!(?state_root_hash, ?effects, "commit");
-    let correlation_id = CorrelationId::new();
-    let start = Instant::now();
-    let result = engine_state.apply_effect(correlation_id, state_root_hash.into(), effects);
-    metrics.apply_effect.observe(start.elapsed().as_secs_f64());
+    trace!(?correlation_id, ?state_root_hash, ?effects, "commit");
+    trace!(
+        transforms = effects.len(),
+        writes = effects
+            .values()
+            .filter(|transform| transform.is_write())
+            .count(),
+        cl_value_writes = effects
+            .values()
+            .filter(|transform| transform.is_cl_value_write())
+            .count(),
+        identities = effects
+            .values()
+            .filter(|transform| transform.is_identity())
+            .count(),
+        "transforms to commit"
+    );
+    let tallies = tally_transforms(&effects);
+    // Only attempts which may be followed by a retry need their own copy of the effects; the
+    // last attempt consumes the original.
+    let mut effects = Some(effects);
+    let result = retry_transient(max_commit_attempts, &metrics, |is_last_attempt| {
+        let attempt_effects = if is_last_attempt {
+            effects
+                .take()
+                .expect("last attempt should only be made once")
+        } else {
+            effects
+                .as_ref()
+                .expect("effects should be held until the last attempt")
+                .clone()
+        };
+        let start = Instant::now();
+        let result =
+            engine_state.apply_effect(correlation_id, state_root_hash.into(), attempt_effects);
+        metrics.apply_effect.observe(start.elapsed().as_secs_f64());
+        result
+    })
+    .await;
     trace!(?result, "commit result");
+    if let Ok(CommitResult::Success { .. }) = result {
+        for (transform_name, count) in tallies {
+            metrics
+                .transforms_committed
+                .with_label_values(&[transform_name])
+                .inc_by(count as u64);
+        }
+    }
     result
 }
 
+/// Counts the transforms in `effects` by transform type.
+fn tally_transforms(effects: &AdditiveMap<Key, Transform>) -> BTreeMap<&'static str, usize> {
+    let mut tallies = BTreeMap::new();
+    for transform in effects.values() {
+        *tallies.entry(transform_name(transform)).or_insert(0) += 1;
+    }
+    tallies
+}
+
+/// Returns the name of the transform's variant, used to label metrics.
+fn transform_name(transform: &Transform) -> &'static str {
+    match transform {
+        Transform::Identity => "identity",
+        Transform::Write(_) => "write",
+        Transform::AddInt32(_) => "add_int32",
+        Transform::AddUInt64(_) => "add_uint64",
+        Transform::AddUInt128(_) => "add_uint128",
+        Transform::AddUInt256(_) => "add_uint256",
+        Transform::AddUInt512(_) => "add_uint512",
+        Transform::AddKeys(_) => "add_keys",
+        Transform::Failure(_) => "failure",
+    }
+}
+
+/// Calls `operation` until it succeeds, fails with a non-transient error, or has been attempted
+/// `max_attempts` times.  Each retry is recorded in the `commit_retries` metric.
+///
+/// `operation` is passed `true` if no retry can follow the attempt.
+async fn retry_transient<T, F>(
+    max_attempts: u32,
+    metrics: &ContractRuntimeMetrics,
+    mut operation: F,
+) -> Result<T, engine_state::Error>
+where
+    F: FnMut(bool) -> Result<T, engine_state::Error>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation(attempt >= max_attempts) {
+            Err(error) if attempt < max_attempts && is_transient(&error) => {
+                warn!(%error, %attempt, "transient commit failure, retrying");
+                metrics.commit_retries.inc();
+                tokio::time::sleep(COMMIT_RETRY_BACKOFF * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Returns `true` if `error` is caused by contention in the global state store, and so may not
+/// recur if the operation is retried.
+fn is_transient(error: &engine_state::Error) -> bool {
+    matches!(
+        error,
+        engine_state::Error::Storage(storage::error::Error::Lmdb(lmdb::Error::ReadersFull))
+    )
+}
+
 pub(super) async fn execute(
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
+    correlation_id: CorrelationId,
     execute_request: ExecuteRequest,
 ) -> Result<VecDeque<EngineExecutionResult>, engine_state::Error> {
-    trace!(?execute_request, "execute");
-    let correlation_id = CorrelationId::new();
+    trace!(?correlation_id, ?execute_request, "execute");
+    let category = deploy_category(&execute_request);
     let start = Instant::now();
     let result = engine_state.run_execute(correlation_id, execute_request);
-    metrics.run_execute.observe(start.elapsed().as_secs_f64());
+    let elapsed = start.elapsed().as_secs_f64();
+    metrics.run_execute.observe(elapsed);
+    metrics
+        .run_execute_by_category
+        .with_label_values(&[category])
+        .observe(elapsed);
     trace!(?result, "execute result");
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use lmdb::DatabaseFlags;
+    use prometheus::Registry;
+    use tempfile::TempDir;
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use casper_execution_engine::{
+        core::engine_state::{
+            executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
+            DeployItem, EngineConfig,
+        },
+        shared::{gas::Gas, stored_value::StoredValue},
+        storage::{
+            global_state::StateProvider, protocol_data::ProtocolData,
+            protocol_data_store::lmdb::LmdbProtocolDataStore,
+            transaction_source::lmdb::LmdbEnvironment, trie_store::lmdb::LmdbTrieStore,
+        },
+    };
+    use casper_types::{account::AccountHash, bytesrepr::Bytes, BlockTime, CLValue, RuntimeArgs};
+
+    use super::{super::tests::LogCapture, *};
+    use crate::{crypto::AsymmetricKeyExt, testing::TestRng, types::Block};
+
+    /// 50 MiB, a multiple of the OS page size.
+    const TEST_MAX_GLOBAL_STATE_SIZE: usize = 52_428_800;
+    const TEST_MAX_READERS: u32 = 512;
+
+    /// Creates an engine state backed by an empty LMDB global state in `tempdir`, returning it
+    /// along with the empty state root hash.
+    fn new_engine_state(tempdir: &TempDir) -> (Arc<EngineState<LmdbGlobalState>>, Digest) {
+        new_engine_state_with_protocol_data(tempdir, &[])
+    }
+
+    /// Like `new_engine_state`, but with default protocol data stored under each of the given
+    /// `protocol_versions`.
+    fn new_engine_state_with_protocol_data(
+        tempdir: &TempDir,
+        protocol_versions: &[ProtocolVersion],
+    ) -> (Arc<EngineState<LmdbGlobalState>>, Digest) {
+        let environment = Arc::new(
+            LmdbEnvironment::new(tempdir.path(), TEST_MAX_GLOBAL_STATE_SIZE, TEST_MAX_READERS)
+                .unwrap(),
+        );
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let global_state =
+            LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        for protocol_version in protocol_versions {
+            global_state
+                .put_protocol_data(*protocol_version, &ProtocolData::default())
+                .unwrap();
+        }
+        let empty_root_hash = global_state.empty_root_hash.into();
+        let engine_state = EngineState::new(global_state, EngineConfig::default());
+        (Arc::new(engine_state), empty_root_hash)
+    }
+
+    fn new_metrics() -> Arc<ContractRuntimeMetrics> {
+        Arc::new(ContractRuntimeMetrics::new(&Registry::new()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn applying_empty_transforms_should_keep_state_root() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, empty_root_hash) = new_engine_state(&tempdir);
+
+        let post_state_root_hash = apply_transforms(
+            engine_state,
+            new_metrics(),
+            empty_root_hash,
+            AdditiveMap::new(),
+            1,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(post_state_root_hash, empty_root_hash);
+    }
+
+    #[test]
+    fn should_sum_reward_items() {
+        let mut rng = TestRng::new();
+        let reward_items = vec![
+            RewardItem::new(PublicKey::random(&mut rng), 1_000),
+            RewardItem::new(PublicKey::random(&mut rng), 250),
+            RewardItem::new(PublicKey::random(&mut rng), u64::MAX),
+        ];
+
+        assert_eq!(
+            total_rewards(&reward_items),
+            U512::from(u64::MAX) + U512::from(1_250)
+        );
+        assert_eq!(total_rewards(&[]), U512::zero());
+    }
+
+    #[test]
+    fn should_count_step_items() {
+        let mut rng = TestRng::new();
+        let era_report = EraReport {
+            equivocators: vec![PublicKey::random(&mut rng)],
+            rewards: (0..2)
+                .map(|reward| (PublicKey::random(&mut rng), reward))
+                .collect(),
+            inactive_validators: (0..3).map(|_| PublicKey::random(&mut rng)).collect(),
+        };
+
+        let expected = StepItemCounts {
+            reward_items: 2,
+            slash_items: 1,
+            evict_items: 3,
+        };
+        assert_eq!(step_item_counts(Some(&era_report)), expected);
+        assert_eq!(step_item_counts(None), StepItemCounts::default());
+    }
+
+    #[test]
+    fn should_evict_inactive_validators_as_inactive() {
+        let mut rng = TestRng::new();
+        let inactive_validators = vec![PublicKey::random(&mut rng), PublicKey::random(&mut rng)];
+
+        let evict_items = evict_items(&inactive_validators);
+
+        assert_eq!(evict_items.len(), inactive_validators.len());
+        for (evict_item, validator_id) in evict_items.iter().zip(&inactive_validators) {
+            assert_eq!(&evict_item.validator_id, validator_id);
+            assert_eq!(evict_item.reason, EvictReason::Inactive);
+        }
+    }
+
+    #[test]
+    fn should_reject_protocol_version_older_than_highest_block() {
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let block = Block::random(&mut rng);
+        let actual = block.header().protocol_version();
+        let expected = ProtocolVersion::from_parts(0, 0, 0);
+        let (engine_state, _) = new_engine_state_with_protocol_data(&tempdir, &[expected, actual]);
+
+        let error = check_protocol_version(&engine_state, block.header(), expected).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ConfigError::ProtocolVersionMismatch {
+                expected: error_expected,
+                actual: error_actual,
+            } if error_expected == expected && error_actual == actual
+        ));
+    }
+
+    #[test]
+    fn should_reject_protocol_version_missing_from_global_state() {
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, _) = new_engine_state(&tempdir);
+        let block = Block::random(&mut rng);
+        let version = block.header().protocol_version();
+
+        let error = check_protocol_version(&engine_state, block.header(), version).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ConfigError::ProtocolVersionMismatch { expected, actual }
+                if expected == version && actual == version
+        ));
+    }
+
+    #[test]
+    fn should_accept_protocol_version_not_older_than_highest_block() {
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let block = Block::random(&mut rng);
+        let version = block.header().protocol_version();
+        let upgraded = ProtocolVersion::from_parts(
+            version.value().major + 1,
+            version.value().minor,
+            version.value().patch,
+        );
+        let (engine_state, _) = new_engine_state_with_protocol_data(&tempdir, &[version, upgraded]);
+
+        assert!(check_protocol_version(&engine_state, block.header(), version).is_ok());
+        assert!(check_protocol_version(&engine_state, block.header(), upgraded).is_ok());
+    }
+
+    fn random_deploy_hashes(rng: &mut TestRng, count: usize) -> Vec<DeployHash> {
+        (0..count)
+            .map(|_| DeployHash::new(Digest::random(rng)))
+            .collect()
+    }
+
+    fn failed_execution_results(count: usize) -> ExecutionResults {
+        (0..count)
+            .map(|_| EngineExecutionResult::precondition_failure(engine_state::Error::Deploy))
+            .collect()
+    }
+
+    #[test]
+    fn should_map_execution_results_to_deploy_hashes() {
+        let mut rng = TestRng::new();
+        let deploy_hashes = random_deploy_hashes(&mut rng, 3);
+
+        let results = results_to_map(&deploy_hashes, failed_execution_results(3)).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(deploy_hashes
+            .iter()
+            .all(|deploy_hash| results.contains_key(deploy_hash)));
+    }
+
+    #[test]
+    fn should_fail_to_map_too_few_execution_results() {
+        let mut rng = TestRng::new();
+        let deploy_hashes = random_deploy_hashes(&mut rng, 3);
+
+        let error = results_to_map(&deploy_hashes, failed_execution_results(2)).unwrap_err();
+
+        assert!(matches!(
+            error,
+            BlockExecutionError::ExecutionResultsMismatch {
+                deploy_hashes: 3,
+                execution_results: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_map_too_many_execution_results() {
+        let mut rng = TestRng::new();
+        let deploy_hashes = random_deploy_hashes(&mut rng, 3);
+
+        let error = results_to_map(&deploy_hashes, failed_execution_results(4)).unwrap_err();
+
+        assert!(matches!(
+            error,
+            BlockExecutionError::ExecutionResultsMismatch {
+                deploy_hashes: 3,
+                execution_results: 4
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_record_execution_time_by_deploy_category() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, empty_root_hash) = new_engine_state(&tempdir);
+        let metrics = new_metrics();
+        let mut rng = TestRng::new();
+
+        let transfer = ExecutableDeployItem::Transfer {
+            args: RuntimeArgs::new(),
+        };
+        let contract = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: RuntimeArgs::new(),
+        };
+        for session in vec![transfer, contract] {
+            let payment = ExecutableDeployItem::ModuleBytes {
+                module_bytes: Bytes::new(),
+                args: RuntimeArgs::new(),
+            };
+            let deploy_item = DeployItem::new(
+                AccountHash::new([1; 32]),
+                session,
+                payment,
+                1,
+                BTreeSet::new(),
+                casper_types::DeployHash::new([2; 32]),
+            );
+            let execute_request = ExecuteRequest::new(
+                empty_root_hash.into(),
+                BlockTime::new(0),
+                vec![deploy_item],
+                ProtocolVersion::V1_0_0,
+                PublicKey::random(&mut rng),
+            );
+            execute(
+                Arc::clone(&engine_state),
+                Arc::clone(&metrics),
+                CorrelationId::new(),
+                execute_request,
+            )
+            .await
+            .unwrap();
+        }
+
+        for category in &["transfer", "contract"] {
+            let histogram = metrics
+                .run_execute_by_category
+                .with_label_values(&[*category]);
+            assert_eq!(histogram.get_sample_count(), 1, "{}", category);
+        }
+        assert_eq!(metrics.run_execute.get_sample_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_count_deploy_successes_and_failures() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, empty_root_hash) = new_engine_state(&tempdir);
+        let metrics = new_metrics();
+        let mut rng = TestRng::new();
+
+        let execution_results = vec![
+            EngineExecutionResult::default(),
+            EngineExecutionResult::precondition_failure(engine_state::Error::Deploy),
+        ];
+        for execution_result in execution_results {
+            commit_execution_effects(
+                Arc::clone(&engine_state),
+                Arc::clone(&metrics),
+                CorrelationId::new(),
+                empty_root_hash,
+                DeployHash::new(Digest::random(&mut rng)),
+                iter::once(execution_result).collect(),
+                1,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(metrics.deploy_success_total.get(), 1);
+        assert_eq!(metrics.deploy_failure_total.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_log_successes_at_trace_only_if_demoted() {
+        for &(demote_success_logs, success_level) in &[(true, Level::TRACE), (false, Level::DEBUG)]
+        {
+            let tempdir = tempfile::tempdir().unwrap();
+            let (engine_state, empty_root_hash) = new_engine_state(&tempdir);
+            let mut rng = TestRng::new();
+            let log_capture = LogCapture::default();
+            let subscriber = tracing_subscriber::Registry::default().with(log_capture.clone());
+            let _guard = tracing::subscriber::set_default(subscriber);
+
+            let execution_results = vec![
+                EngineExecutionResult::default(),
+                EngineExecutionResult::precondition_failure(engine_state::Error::Deploy),
+            ];
+            for execution_result in execution_results {
+                commit_execution_effects(
+                    Arc::clone(&engine_state),
+                    new_metrics(),
+                    CorrelationId::new(),
+                    empty_root_hash,
+                    DeployHash::new(Digest::random(&mut rng)),
+                    iter::once(execution_result).collect(),
+                    1,
+                    demote_success_logs,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+
+            assert_eq!(
+                log_capture.level_of("execution succeeded"),
+                Some(success_level)
+            );
+            assert_eq!(
+                log_capture.level_of("commit succeeded"),
+                Some(success_level)
+            );
+            assert_eq!(
+                log_capture.level_of("execution failure"),
+                Some(Level::DEBUG)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_capture_transform_counts() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, mut state_root_hash) = new_engine_state(&tempdir);
+        let mut rng = TestRng::new();
+
+        let mut expected = Vec::new();
+        let mut transform_counts = Vec::new();
+        for transform_count in 0..3u8 {
+            let transforms: AdditiveMap<Key, Transform> = (0..transform_count)
+                .map(|index| {
+                    let value = CLValue::from_t(u64::from(index)).unwrap();
+                    (
+                        Key::Hash([index; 32]),
+                        Transform::Write(StoredValue::CLValue(value)),
+                    )
+                })
+                .collect();
+            let execution_result = EngineExecutionResult::Success {
+                effect: ExecutionEffect::new(AdditiveMap::new(), transforms),
+                transfers: Vec::new(),
+                cost: Gas::default(),
+            };
+            let deploy_hash = DeployHash::new(Digest::random(&mut rng));
+            expected.push((deploy_hash, usize::from(transform_count)));
+
+            let (post_state_root_hash, _) = commit_execution_effects(
+                Arc::clone(&engine_state),
+                new_metrics(),
+                CorrelationId::new(),
+                state_root_hash,
+                deploy_hash,
+                iter::once(execution_result).collect(),
+                1,
+                true,
+                Some(&mut transform_counts),
+                None,
+            )
+            .await
+            .unwrap();
+            state_root_hash = post_state_root_hash;
+        }
+
+        assert_eq!(transform_counts, expected);
+    }
+
+    #[tokio::test]
+    async fn should_collect_touched_keys() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, mut state_root_hash) = new_engine_state(&tempdir);
+        let mut rng = TestRng::new();
+
+        let deploy_keys = vec![
+            vec![Key::Hash([0; 32]), Key::Hash([1; 32])],
+            vec![Key::Hash([1; 32]), Key::Hash([2; 32])],
+        ];
+        let mut touched_keys = BTreeSet::new();
+        for keys in &deploy_keys {
+            let transforms: AdditiveMap<Key, Transform> = keys
+                .iter()
+                .map(|key| {
+                    (
+                        *key,
+                        Transform::Write(StoredValue::CLValue(CLValue::unit())),
+                    )
+                })
+                .collect();
+            let execution_result = EngineExecutionResult::Success {
+                effect: ExecutionEffect::new(AdditiveMap::new(), transforms),
+                transfers: Vec::new(),
+                cost: Gas::default(),
+            };
+
+            let (post_state_root_hash, _) = commit_execution_effects(
+                Arc::clone(&engine_state),
+                new_metrics(),
+                CorrelationId::new(),
+                state_root_hash,
+                DeployHash::new(Digest::random(&mut rng)),
+                iter::once(execution_result).collect(),
+                1,
+                true,
+                None,
+                Some(&mut touched_keys),
+            )
+            .await
+            .unwrap();
+            state_root_hash = post_state_root_hash;
+        }
+
+        let expected: BTreeSet<Key> = deploy_keys.into_iter().flatten().collect();
+        assert_eq!(touched_keys, expected);
+    }
+
+    #[tokio::test]
+    async fn should_count_state_root_changes() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let (engine_state, empty_root_hash) = new_engine_state(&tempdir);
+        let metrics = new_metrics();
+
+        // A block without deploys leaves the state root as it was.
+        record_state_root_change(&metrics, empty_root_hash, empty_root_hash);
+        assert_eq!(metrics.state_root_unchanged_total.get(), 1);
+        assert_eq!(metrics.state_root_advances_total.get(), 0);
+
+        // A block whose deploy writes to global state moves it.
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            Key::Hash([0; 32]),
+            Transform::Write(StoredValue::CLValue(CLValue::unit())),
+        );
+        let post_state_root_hash =
+            apply_transforms(engine_state, Arc::clone(&metrics), empty_root_hash, effects)
+                .await
+                .unwrap();
+        record_state_root_change(&metrics, empty_root_hash, post_state_root_hash);
+        assert_eq!(metrics.state_root_unchanged_total.get(), 1);
+        assert_eq!(metrics.state_root_advances_total.get(), 1);
+    }
+
+    #[test]
+    fn should_tally_transforms_by_type() {
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            Key::Hash([0; 32]),
+            Transform::Write(StoredValue::CLValue(CLValue::unit())),
+        );
+        effects.insert(
+            Key::Hash([1; 32]),
+            Transform::Write(StoredValue::CLValue(CLValue::unit())),
+        );
+        effects.insert(Key::Hash([2; 32]), Transform::AddInt32(1));
+        effects.insert(Key::Hash([3; 32]), Transform::AddUInt512(1u64.into()));
+        effects.insert(Key::Hash([4; 32]), Transform::Identity);
+
+        let tallies = tally_transforms(&effects);
+
+        let expected: BTreeMap<_, _> = vec![
+            ("write", 2),
+            ("add_int32", 1),
+            ("add_uint512", 1),
+            ("identity", 1),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(tallies, expected);
+    }
+
+    fn transient_error() -> engine_state::Error {
+        engine_state::Error::Storage(lmdb::Error::ReadersFull.into())
+    }
+
+    #[tokio::test]
+    async fn should_retry_transient_failure() {
+        let metrics = new_metrics();
+        let mut calls = 0;
+        let result = retry_transient(3, &metrics, |_| {
+            calls += 1;
+            if calls == 1 {
+                Err(transient_error())
+            } else {
+                Ok(calls)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(metrics.commit_retries.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_beyond_max_attempts() {
+        let metrics = new_metrics();
+        let mut last_attempt_flags = Vec::new();
+        let result: Result<(), _> = retry_transient(3, &metrics, |is_last_attempt| {
+            last_attempt_flags.push(is_last_attempt);
+            Err(transient_error())
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(last_attempt_flags, vec![false, false, true]);
+        assert_eq!(metrics.commit_retries.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_fail_fast_on_non_transient_failure() {
+        let metrics = new_metrics();
+        let mut calls = 0;
+        let result: Result<(), _> = retry_transient(3, &metrics, |_| {
+            calls += 1;
+            Err(engine_state::Error::Deploy)
+        })
+        .await;
+
+        assert!(matches!(result, Err(engine_state::Error::Deploy)));
+        assert_eq!(calls, 1);
+        assert_eq!(metrics.commit_retries.get(), 0);
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use derive_more::From;
+use prometheus::Registry;
+use rand::Rng;
+use tempfile::TempDir;
+use tracing::{
+    field::{Field, Visit},
+    Level, Subscriber,
+};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+use casper_types::{EraId, ExecutionResult, ProtocolVersion};
+
+use super::{Config, ConfigError, ContractRuntime, Event, RequestState};
+use crate::{
+    crypto::hash::Digest,
+    effect::{
+        announcements::{ContractRuntimeAnnouncement, ControlAnnouncement},
+        requests::{ConsensusRequest, ContractRuntimeRequest, LinearChainRequest, StorageRequest},
+        Effects,
+    },
+    reactor::ReactorEvent,
+    testing::{ComponentHarness, TestRng},
+    types::{Block, Deploy, DeployHash, FinalizedBlock, NodeId},
+    utils::WithDir,
+    StorageConfig,
+};
+
+/// A reactor event for unit tests which preserves the requests and announcements made by the
+/// contract runtime, discarding everything else.
+#[derive(Debug, From)]
+enum TestEvent {
+    #[from]
+    ContractRuntimeRequest(ContractRuntimeRequest),
+    #[from]
+    ContractRuntimeAnnouncement(ContractRuntimeAnnouncement),
+    #[from]
+    ControlAnnouncement(ControlAnnouncement),
+    Other,
+}
+
+impl From<Event> for TestEvent {
+    fn from(_: Event) -> Self {
+        TestEvent::Other
+    }
+}
+
+impl From<StorageRequest> for TestEvent {
+    fn from(_: StorageRequest) -> Self {
+        TestEvent::Other
+    }
+}
+
+impl From<LinearChainRequest<NodeId>> for TestEvent {
+    fn from(_: LinearChainRequest<NodeId>) -> Self {
+        TestEvent::Other
+    }
+}
+
+impl From<ConsensusRequest> for TestEvent {
+    fn from(_: ConsensusRequest) -> Self {
+        TestEvent::Other
+    }
+}
+
+impl ReactorEvent for TestEvent {
+    fn as_control(&self) -> Option<&ControlAnnouncement> {
+        match self {
+            TestEvent::ControlAnnouncement(ctrl_ann) => Some(ctrl_ann),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of a log event, rendered with their `Debug` implementations.
+#[derive(Default)]
+struct LogFields(BTreeMap<String, String>);
+
+impl LogFields {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+impl Visit for LogFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// A tracing layer which records the level and fields of every log event.
+#[derive(Clone, Default)]
+pub(super) struct LogCapture(Arc<Mutex<Vec<(Level, LogFields)>>>);
+
+impl LogCapture {
+    /// Returns the level of the first captured event with the given `message`.
+    pub(super) fn level_of(&self, message: &str) -> Option<Level> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, fields)| fields.get("message") == Some(message))
+            .map(|(level, _)| *level)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogCapture {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = LogFields::default();
+        event.record(&mut fields);
+        self.0
+            .lock()
+            .unwrap()
+            .push((*event.metadata().level(), fields));
+    }
+}
+
+/// Creates a contract runtime without an initial block, backed by global state in a new temporary
+/// directory which is returned alongside it.
+fn new_contract_runtime() -> (ContractRuntime, TempDir) {
+    let (storage_config, storage_tempdir) = StorageConfig::default_for_tests();
+    let contract_runtime = ContractRuntime::new(
+        Digest::default(),
+        None,
+        ProtocolVersion::V1_0_0,
+        WithDir::new(storage_tempdir.path(), storage_config),
+        &Config::default(),
+        &Registry::new(),
+    )
+    .unwrap();
+    (contract_runtime, storage_tempdir)
+}
+
+/// Runs the `effects` on the harness' runtime and returns the first event they schedule.
+fn first_scheduled_event(
+    harness: &mut ComponentHarness<TestEvent>,
+    effects: Effects<Event>,
+) -> TestEvent {
+    for effect in effects {
+        harness.runtime.spawn(effect);
+    }
+    let (event, _queue_kind) = harness.runtime.block_on(harness.scheduler.pop());
+    event
+}
+
+#[test]
+fn should_run_step_with_correlation_id_of_block() {
+    let mut harness = ComponentHarness::<TestEvent>::default();
+    let (mut contract_runtime, _storage_tempdir) = new_contract_runtime();
+    let switch_block =
+        FinalizedBlock::random_with_specifics(&mut harness.rng, EraId::from(1), 0, true);
+    let state = Box::new(RequestState::new(
+        switch_block,
+        VecDeque::new(),
+        Digest::random(&mut harness.rng),
+    ));
+    let expected_correlation_id = state.correlation_id;
+
+    let effects = contract_runtime.finalize_block_or_step(harness.effect_builder, state);
+
+    match first_scheduled_event(&mut harness, effects) {
+        TestEvent::ContractRuntimeRequest(ContractRuntimeRequest::Step {
+            correlation_id, ..
+        }) => assert_eq!(correlation_id, expected_correlation_id),
+        event => panic!("expected a step request, got {:?}", event),
+    }
+}
+
+#[test]
+fn should_announce_execution_results_ordered_by_deploy_hash() {
+    let mut harness = ComponentHarness::<TestEvent>::default();
+    let (mut contract_runtime, _storage_tempdir) = new_contract_runtime();
+    let block = FinalizedBlock::random_with_specifics(&mut harness.rng, EraId::from(0), 0, false);
+    let mut state = Box::new(RequestState::new(
+        block,
+        VecDeque::new(),
+        Digest::random(&mut harness.rng),
+    ));
+    let mut deploy_hashes: Vec<DeployHash> = (0..5)
+        .map(|_| DeployHash::random(&mut harness.rng))
+        .collect();
+    deploy_hashes.sort();
+    // Record the results in the reverse of the expected order.
+    for deploy_hash in deploy_hashes.iter().rev() {
+        let deploy_header = Deploy::random(&mut harness.rng).header().clone();
+        let execution_result: ExecutionResult = harness.rng.gen();
+        state
+            .execution_results
+            .insert(*deploy_hash, (deploy_header, execution_result));
+    }
+
+    let effects = contract_runtime.finalize_block_or_step(harness.effect_builder, state);
+
+    match first_scheduled_event(&mut harness, effects) {
+        TestEvent::ContractRuntimeAnnouncement(ContractRuntimeAnnouncement::LinearChainBlock(
+            linear_chain_block,
+        )) => {
+            let announced: Vec<DeployHash> = linear_chain_block
+                .execution_results
+                .keys()
+                .copied()
+                .collect();
+            assert_eq!(announced, deploy_hashes);
+        }
+        event => panic!(
+            "expected a linear chain block announcement, got {:?}",
+            event
+        ),
+    }
+}
+
+#[test]
+fn should_log_era_id_when_starting_block_without_deploys() {
+    let mut harness = ComponentHarness::<TestEvent>::default();
+    let (mut contract_runtime, _storage_tempdir) = new_contract_runtime();
+    let block = FinalizedBlock::random_with_specifics(&mut harness.rng, EraId::from(7), 0, false);
+    let log_capture = LogCapture::default();
+    let subscriber = tracing_subscriber::Registry::default().with(log_capture.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        contract_runtime.handle_get_deploys_result(harness.effect_builder, block, VecDeque::new())
+    });
+
+    let logs = log_capture.0.lock().unwrap();
+    let (_, block_start) = logs
+        .iter()
+        .find(|(_, fields)| fields.get("message") == Some("executing block"))
+        .expect("should log the start of block execution");
+    assert_eq!(block_start.get("era_id"), Some("era 7"));
+    assert_eq!(block_start.get("deploys"), Some("0"));
+}
+
+#[test]
+fn should_reject_initial_state_of_later_protocol_version() {
+    let mut rng = TestRng::new();
+    let (mut contract_runtime, _storage_tempdir) = new_contract_runtime();
+    let later_version = ProtocolVersion::from_parts(2, 0, 0);
+    let block = Block::random_with_specifics(&mut rng, EraId::from(1), 1, later_version, false);
+
+    let error = contract_runtime
+        .set_initial_state(*block.header().state_root_hash(), Some(block.header()))
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ConfigError::ProtocolVersionMismatch { expected, actual }
+            if expected == ProtocolVersion::V1_0_0 && actual == later_version
+    ));
+}
@@ -64,7 +64,7 @@ pub mod requests;
 use std::{
     any::type_name,
     borrow::Cow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     future::Future,
     sync::Arc,
@@ -92,7 +92,10 @@ use casper_execution_engine::{
         BalanceRequest, BalanceResult, GetBidsRequest, GetBidsResult, QueryRequest, QueryResult,
         MAX_PAYMENT,
     },
-    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
+    },
     storage::{protocol_data::ProtocolData, trie::Trie},
 };
 use casper_types::{
@@ -706,13 +709,20 @@ impl<REv> EffectBuilder<REv> {
     pub(crate) async fn announce_linear_chain_block(
         self,
         block: Block,
-        execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        execution_results: BTreeMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        transform_counts: Vec<(DeployHash, usize)>,
+        touched_keys: BTreeSet<Key>,
     ) where
         REv: From<ContractRuntimeAnnouncement>,
     {
         self.0
             .schedule(
-                ContractRuntimeAnnouncement::linear_chain_block(block, execution_results),
+                ContractRuntimeAnnouncement::linear_chain_block(
+                    block,
+                    execution_results,
+                    transform_counts,
+                    touched_keys,
+                ),
                 QueueKind::Regular,
             )
             .await
@@ -1570,6 +1580,7 @@ impl<REv> EffectBuilder<REv> {
     /// Runs the end of era step using the system smart contract.
     pub(crate) async fn run_step(
         self,
+        correlation_id: CorrelationId,
         step_request: StepRequest,
     ) -> Result<StepResult, engine_state::Error>
     where
@@ -1577,6 +1588,7 @@ impl<REv> EffectBuilder<REv> {
     {
         self.make_request(
             |responder| ContractRuntimeRequest::Step {
+                correlation_id,
                 step_request,
                 responder,
             },
@@ -4,13 +4,13 @@
 //! module documentation for details.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Display, Formatter},
 };
 
 use serde::Serialize;
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey};
+use casper_types::{EraId, ExecutionEffect, ExecutionResult, Key, PublicKey};
 
 use crate::{
     components::{
@@ -235,11 +235,15 @@ impl ContractRuntimeAnnouncement {
     /// Create a ContractRuntimeAnnouncement::LinearChainBlock from it's parts.
     pub fn linear_chain_block(
         block: Block,
-        execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        execution_results: BTreeMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        transform_counts: Vec<(DeployHash, usize)>,
+        touched_keys: BTreeSet<Key>,
     ) -> Self {
         Self::LinearChainBlock(Box::new(LinearChainBlock {
             block,
             execution_results,
+            transform_counts,
+            touched_keys,
         }))
     }
     /// Create a ContractRuntimeAnnouncement::BlockAlreadyExecuted from a Block.
@@ -262,7 +266,11 @@ pub struct LinearChainBlock {
     /// The block.
     pub block: Block,
     /// The results of executing the deploys in this block.
-    pub execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    pub execution_results: BTreeMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    /// The number of transforms committed for each deploy in this block, in execution order.
+    pub transform_counts: Vec<(DeployHash, usize)>,
+    /// The keys of all transforms committed for the deploys in this block.
+    pub touched_keys: BTreeSet<Key>,
 }
 
 impl Display for ContractRuntimeAnnouncement {
@@ -26,7 +26,10 @@ use casper_execution_engine::{
         step::{StepRequest, StepResult},
         upgrade::{UpgradeConfig, UpgradeResult},
     },
-    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
+    },
     storage::{protocol_data::ProtocolData, trie::Trie},
 };
 use casper_types::{
@@ -784,6 +787,8 @@ pub enum ContractRuntimeRequest {
     /// Performs a step consisting of calculating rewards, slashing and running the auction at the
     /// end of an era.
     Step {
+        /// The correlation id of the execution of the block triggering the step.
+        correlation_id: CorrelationId,
         /// The step request.
         #[serde(skip_serializing)]
         step_request: StepRequest,
@@ -473,7 +473,7 @@ impl reactor::Reactor for Reactor {
         contract_runtime.set_initial_state(
             chainspec_loader.initial_state_root_hash(),
             chainspec_loader.initial_block_header(),
-        );
+        )?;
 
         let linear_chain = linear_chain::LinearChainComponent::new(
             registry,
@@ -736,6 +736,7 @@ impl reactor::Reactor for Reactor {
                 let LinearChainBlock {
                     block,
                     execution_results,
+                    ..
                 } = *linear_chain_block;
                 let mut effects = Effects::new();
                 let block_hash = *block.hash();
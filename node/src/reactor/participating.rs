@@ -485,7 +485,7 @@ impl reactor::Reactor for Reactor {
         contract_runtime.set_initial_state(
             chainspec_loader.initial_state_root_hash(),
             chainspec_loader.initial_block_header(),
-        );
+        )?;
         contract_runtime.set_parent_map_from_block(maybe_latest_block_header);
 
         let block_validator = BlockValidator::new(Arc::clone(chainspec_loader.chainspec()));
@@ -984,6 +984,7 @@ impl reactor::Reactor for Reactor {
                 let LinearChainBlock {
                     block,
                     execution_results,
+                    ..
                 } = *linear_chain_block;
                 let mut effects = Effects::new();
                 let block_hash = *block.hash();
@@ -359,6 +359,11 @@ impl FinalizedBlock {
         self.era_report.as_ref()
     }
 
+    /// Returns `true` if this is a switch block, i.e. the last block of its era.
+    pub(crate) fn is_switch_block(&self) -> bool {
+        self.era_report.is_some()
+    }
+
     /// Returns the ID of the era this block belongs to.
     pub(crate) fn era_id(&self) -> EraId {
         self.era_id
@@ -1669,6 +1674,18 @@ mod tests {
         assert_eq!(finalized_block, decoded);
     }
 
+    #[test]
+    fn finalized_block_should_report_switch_block() {
+        let mut rng = TestRng::new();
+        let switch_block =
+            FinalizedBlock::random_with_specifics(&mut rng, EraId::from(1), 10, true);
+        assert!(switch_block.is_switch_block());
+
+        let non_switch_block =
+            FinalizedBlock::random_with_specifics(&mut rng, EraId::from(1), 11, false);
+        assert!(!non_switch_block.is_switch_block());
+    }
+
     #[test]
     fn block_bytesrepr_roundtrip() {
         let mut rng = TestRng::new();
@@ -17,7 +17,10 @@ use rand::Rng;
 use schemars::JsonSchema;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
-use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime,
+};
 
 use crate::rpcs::docs::DocExample;
 
@@ -184,6 +187,13 @@ impl From<u64> for Timestamp {
     }
 }
 
+/// The execution engine's block time is the number of milliseconds since the Unix epoch.
+impl From<Timestamp> for BlockTime {
+    fn from(timestamp: Timestamp) -> BlockTime {
+        BlockTime::new(timestamp.0)
+    }
+}
+
 /// A time difference between two timestamps.
 #[derive(
     Debug,
@@ -355,4 +365,10 @@ mod tests {
 
         bytesrepr::test_serialization_roundtrip(&timediff);
     }
+
+    #[test]
+    fn block_time_should_be_millis_since_epoch() {
+        let timestamp = Timestamp::from(1_605_573_564_072);
+        assert_eq!(u64::from(BlockTime::from(timestamp)), timestamp.millis());
+    }
 }
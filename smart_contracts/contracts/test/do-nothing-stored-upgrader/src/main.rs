@@ -12,15 +12,36 @@ use casper_contract::{
 use core::convert::TryInto;
 
 use casper_types::{
-    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
-    CLType, CLTyped, ContractPackageHash, Key, Parameter, URef,
+    contracts::{
+        ContractVersion, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys,
+    },
+    ApiError, CLType, CLTyped, CLValue, ContractPackageHash, Key, Parameter, URef,
 };
 
 const ENTRY_FUNCTION_NAME: &str = "delegate";
+const GET_VERSION_FUNCTION_NAME: &str = "get_version";
 const DO_NOTHING_PACKAGE_HASH_KEY_NAME: &str = "do_nothing_package_hash";
 const DO_NOTHING_ACCESS_KEY_NAME: &str = "do_nothing_access";
 const CONTRACT_VERSION: &str = "contract_version";
 const ARG_PURSE_NAME: &str = "purse_name";
+/// The version the do-nothing contract is at once this upgrade has been applied.
+const TARGET_CONTRACT_VERSION: ContractVersion = 2;
+
+/// Revert status codes:
+/// 1 - the recorded contract version is already at or beyond `TARGET_CONTRACT_VERSION`.
+#[repr(u16)]
+enum Error {
+    AlreadyUpgraded = 1,
+}
+
+fn read_contract_version(contract_version_key: Key) -> ContractVersion {
+    let contract_version_uref: URef = contract_version_key
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    storage::read(contract_version_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound)
+}
 
 #[no_mangle]
 pub extern "C" fn delegate() {
@@ -28,8 +49,22 @@ pub extern "C" fn delegate() {
     create_purse_01::delegate()
 }
 
+#[no_mangle]
+pub extern "C" fn get_version() {
+    let contract_version_key =
+        runtime::get_key(CONTRACT_VERSION).unwrap_or_revert_with(ApiError::MissingKey);
+    let contract_version = read_contract_version(contract_version_key);
+    runtime::ret(CLValue::from_t(contract_version).unwrap_or_revert())
+}
+
 #[no_mangle]
 pub extern "C" fn call() {
+    if let Some(contract_version_key) = runtime::get_key(CONTRACT_VERSION) {
+        if read_contract_version(contract_version_key) >= TARGET_CONTRACT_VERSION {
+            runtime::revert(ApiError::User(Error::AlreadyUpgraded as u16));
+        }
+    }
+
     let entry_points = {
         let mut entry_points = EntryPoints::new();
 
@@ -40,7 +75,20 @@ pub extern "C" fn call() {
             EntryPointAccess::Public,
             EntryPointType::Session,
         );
-        entry_points.add_entry_point(delegate);
+        entry_points
+            .try_add_entry_point(delegate)
+            .unwrap_or_revert();
+
+        let get_version = EntryPoint::new(
+            GET_VERSION_FUNCTION_NAME.to_string(),
+            vec![],
+            ContractVersion::cl_type(),
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points
+            .try_add_entry_point(get_version)
+            .unwrap_or_revert();
 
         entry_points
     };
@@ -57,8 +105,16 @@ pub extern "C" fn call() {
         .try_into()
         .unwrap_or_revert();
 
+    // The same uref is readable by `get_version` through the contract's named keys and by later
+    // upgrade attempts through the installing account's named keys.  Its value is set once the new
+    // version is known.
+    let contract_version_uref = storage::new_uref(ContractVersion::default());
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(CONTRACT_VERSION.to_string(), contract_version_uref.into());
+
     let (contract_hash, contract_version) =
-        storage::add_contract_version(do_nothing_package_hash, entry_points, NamedKeys::new());
-    runtime::put_key(CONTRACT_VERSION, storage::new_uref(contract_version).into());
+        storage::add_contract_version(do_nothing_package_hash, entry_points, named_keys);
+    storage::write(contract_version_uref, contract_version);
+    runtime::put_key(CONTRACT_VERSION, contract_version_uref.into());
     runtime::put_key("end of upgrade", contract_hash.into());
 }
@@ -65,6 +65,10 @@ pub enum Error {
     GroupInUse = 8,
     /// URef already exists in given group.
     URefAlreadyExists = 9,
+    /// Attempted to add an entry point with the same name as an existing one.
+    EntryPointAlreadyExists = 10,
+    /// Attempted to create an entry point with two parameters of the same name.
+    DuplicateParameter = 11,
 }
 
 /// Associated error type of `TryFrom<&[u8]>` for `ContractHash`.
@@ -870,6 +874,16 @@ impl EntryPoints {
         self.0.insert(entry_point.name().to_string(), entry_point);
     }
 
+    /// Adds new [`EntryPoint`], unless an entry point with the same name has already been added,
+    /// in which case [`Error::EntryPointAlreadyExists`] is returned and `self` is left unchanged.
+    pub fn try_add_entry_point(&mut self, entry_point: EntryPoint) -> Result<(), Error> {
+        if self.has_entry_point(entry_point.name()) {
+            return Err(Error::EntryPointAlreadyExists);
+        }
+        self.add_entry_point(entry_point);
+        Ok(())
+    }
+
     /// Checks if given [`EntryPoint`] exists.
     pub fn has_entry_point(&self, entry_point_name: &str) -> bool {
         self.0.contains_key(entry_point_name)
@@ -1161,6 +1175,22 @@ impl EntryPoint {
         }
     }
 
+    /// `EntryPoint` constructor which returns [`Error::DuplicateParameter`] if two of the `args`
+    /// share a name.
+    pub fn new_checked<T: Into<String>>(
+        name: T,
+        args: Parameters,
+        ret: CLType,
+        access: EntryPointAccess,
+        entry_point_type: EntryPointType,
+    ) -> Result<Self, Error> {
+        let mut names = BTreeSet::new();
+        if !args.iter().all(|arg| names.insert(arg.name())) {
+            return Err(Error::DuplicateParameter);
+        }
+        Ok(EntryPoint::new(name, args, ret, access, entry_point_type))
+    }
+
     /// Create a default [`EntryPoint`] with specified name.
     pub fn default_with_name<T: Into<String>>(name: T) -> Self {
         EntryPoint {
@@ -1193,6 +1223,51 @@ impl EntryPoint {
     pub fn entry_point_type(&self) -> EntryPointType {
         self.entry_point_type
     }
+
+    /// Returns an [`EntryPointBuilder`] for an `EntryPoint` with the specified name, starting from
+    /// the same defaults as [`EntryPoint::default`].
+    pub fn builder<T: Into<String>>(name: T) -> EntryPointBuilder {
+        EntryPointBuilder {
+            entry_point: EntryPoint::default_with_name(name),
+        }
+    }
+}
+
+/// Builder for an [`EntryPoint`], avoiding the positional arguments of [`EntryPoint::new`].
+#[derive(Debug, Clone)]
+pub struct EntryPointBuilder {
+    entry_point: EntryPoint,
+}
+
+impl EntryPointBuilder {
+    /// Appends an argument.
+    pub fn arg(mut self, parameter: Parameter) -> Self {
+        self.entry_point.args.push(parameter);
+        self
+    }
+
+    /// Sets the return type.
+    pub fn ret(mut self, ret: CLType) -> Self {
+        self.entry_point.ret = ret;
+        self
+    }
+
+    /// Sets the access.
+    pub fn access(mut self, access: EntryPointAccess) -> Self {
+        self.entry_point.access = access;
+        self
+    }
+
+    /// Sets the entry point type.
+    pub fn entry_point_type(mut self, entry_point_type: EntryPointType) -> Self {
+        self.entry_point.entry_point_type = entry_point_type;
+        self
+    }
+
+    /// Returns the built [`EntryPoint`].
+    pub fn build(self) -> EntryPoint {
+        self.entry_point
+    }
 }
 
 impl Default for EntryPoint {
@@ -1514,6 +1589,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entry_point_builder_should_match_constructor() {
+        let constructed = EntryPoint::new(
+            "delegate",
+            vec![
+                Parameter::new("purse_name", CLType::String),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::U32,
+            EntryPointAccess::groups(&["Group 1"]),
+            EntryPointType::Contract,
+        );
+        let built = EntryPoint::builder("delegate")
+            .arg(Parameter::new("purse_name", CLType::String))
+            .arg(Parameter::new("amount", CLType::U512))
+            .ret(CLType::U32)
+            .access(EntryPointAccess::groups(&["Group 1"]))
+            .entry_point_type(EntryPointType::Contract)
+            .build();
+        assert_eq!(built, constructed);
+
+        assert_eq!(
+            EntryPoint::builder(DEFAULT_ENTRY_POINT_NAME).build(),
+            EntryPoint::default()
+        );
+    }
+
+    #[test]
+    fn should_reject_duplicate_entry_point_name() {
+        let mut entry_points = EntryPoints::new();
+        let delegate = EntryPoint::default_with_name("delegate");
+        assert_eq!(entry_points.try_add_entry_point(delegate.clone()), Ok(()));
+
+        let duplicate = EntryPoint::builder("delegate").ret(CLType::U32).build();
+        assert_eq!(
+            entry_points.try_add_entry_point(duplicate),
+            Err(Error::EntryPointAlreadyExists)
+        );
+        assert_eq!(entry_points.get("delegate"), Some(&delegate));
+    }
+
+    #[test]
+    fn should_reject_duplicate_parameter_names() {
+        let error = EntryPoint::new_checked(
+            "delegate",
+            vec![
+                Parameter::new("amount", CLType::U512),
+                Parameter::new("validator", CLType::PublicKey),
+                Parameter::new("amount", CLType::U64),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+        .unwrap_err();
+        assert_eq!(error, Error::DuplicateParameter);
+
+        let args = vec![
+            Parameter::new("amount", CLType::U512),
+            Parameter::new("validator", CLType::PublicKey),
+        ];
+        let entry_point = EntryPoint::new_checked(
+            "delegate",
+            args.clone(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+        .unwrap();
+        assert_eq!(entry_point.args(), args.as_slice());
+    }
+
     #[test]
     fn contract_hash_from_slice() {
         let bytes: Vec<u8> = (0..32).collect();
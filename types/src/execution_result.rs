@@ -138,6 +138,18 @@ pub enum ExecutionResult {
 }
 
 impl ExecutionResult {
+    /// Returns `true` if the deploy executed successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self, ExecutionResult::Success { .. })
+    }
+
+    /// Returns the cost of executing the deploy.
+    pub fn cost(&self) -> U512 {
+        match self {
+            ExecutionResult::Failure { cost, .. } | ExecutionResult::Success { cost, .. } => *cost,
+        }
+    }
+
     // This method is not intended to be used by third party crates.
     #[doc(hidden)]
     #[cfg(feature = "std")]
@@ -677,6 +689,26 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&transform);
     }
 
+    #[test]
+    fn should_report_success_and_cost() {
+        let success = ExecutionResult::Success {
+            effect: ExecutionEffect::default(),
+            transfers: Vec::new(),
+            cost: U512::from(10),
+        };
+        assert!(success.is_success());
+        assert_eq!(success.cost(), U512::from(10));
+
+        let failure = ExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: Vec::new(),
+            cost: U512::from(20),
+            error_message: String::from("out of gas"),
+        };
+        assert!(!failure.is_success());
+        assert_eq!(failure.cost(), U512::from(20));
+    }
+
     #[test]
     fn bytesrepr_test_execution_result() {
         let mut rng = get_rng();
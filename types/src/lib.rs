@@ -64,8 +64,8 @@ pub use contract_wasm::{ContractWasm, ContractWasmHash};
 #[doc(inline)]
 pub use contracts::{
     Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
-    ContractVersionKey, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group,
-    Parameter,
+    ContractVersionKey, EntryPoint, EntryPointAccess, EntryPointBuilder, EntryPointType,
+    EntryPoints, Group, Parameter,
 };
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
@@ -1,22 +1,28 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{convert::TryFrom, iter, mem, slice::Iter};
 
 #[cfg(feature = "std")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
-    CLType, CLTyped, PublicKey, U512,
+    bytesrepr::{self, FromBytes, ToBytes, U512_SERIALIZED_LENGTH},
+    system::auction::Error,
+    CLType, CLTyped, EraId, PublicKey, U512,
 };
 
 const SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG: u8 = 0;
 const SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG: u8 = 1;
 
 /// Information about a seigniorage allocation
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub enum SeigniorageAllocation {
@@ -60,6 +66,35 @@ impl SeigniorageAllocation {
         }
     }
 
+    /// Constructs a [`SeigniorageAllocation::Validator`], returning [`Error::InvalidAmount`] if
+    /// `amount` is zero.
+    pub fn try_validator(validator_public_key: PublicKey, amount: U512) -> Result<Self, Error> {
+        if amount.is_zero() {
+            return Err(Error::InvalidAmount);
+        }
+        Ok(SeigniorageAllocation::validator(
+            validator_public_key,
+            amount,
+        ))
+    }
+
+    /// Constructs a [`SeigniorageAllocation::Delegator`], returning [`Error::InvalidAmount`] if
+    /// `amount` is zero.
+    pub fn try_delegator(
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+    ) -> Result<Self, Error> {
+        if amount.is_zero() {
+            return Err(Error::InvalidAmount);
+        }
+        Ok(SeigniorageAllocation::delegator(
+            delegator_public_key,
+            validator_public_key,
+            amount,
+        ))
+    }
+
     /// Returns the amount for a given seigniorage allocation
     pub fn amount(&self) -> &U512 {
         match self {
@@ -68,6 +103,97 @@ impl SeigniorageAllocation {
         }
     }
 
+    /// Appends the serialized amount to `out`, producing the same bytes as
+    /// `self.amount().to_bytes()` without allocating a new buffer.
+    pub fn amount_write_bytes(&self, out: &mut Vec<u8>) {
+        let mut little_endian = [0u8; U512_SERIALIZED_LENGTH];
+        self.amount().to_little_endian(&mut little_endian);
+        let num_bytes = little_endian
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |position| position + 1);
+        out.push(num_bytes as u8);
+        out.extend_from_slice(&little_endian[..num_bytes]);
+    }
+
+    fn amount_mut(&mut self) -> &mut U512 {
+        match self {
+            SeigniorageAllocation::Validator { amount, .. } => amount,
+            SeigniorageAllocation::Delegator { amount, .. } => amount,
+        }
+    }
+
+    /// Returns `true` if this is a [`SeigniorageAllocation::Validator`].
+    pub const fn is_validator(&self) -> bool {
+        matches!(self, SeigniorageAllocation::Validator { .. })
+    }
+
+    /// Returns `true` if this is a [`SeigniorageAllocation::Delegator`].
+    pub const fn is_delegator(&self) -> bool {
+        matches!(self, SeigniorageAllocation::Delegator { .. })
+    }
+
+    /// Returns `true` if `self` and `other` are the same kind of allocation to the same
+    /// recipient(s), regardless of their amounts.
+    pub fn same_recipient(&self, other: &SeigniorageAllocation) -> bool {
+        self.recipient_id() == other.recipient_id()
+    }
+
+    /// Returns the sum of the amounts of `allocations`, or `None` if the sum would overflow.
+    pub fn checked_total(allocations: &[SeigniorageAllocation]) -> Option<U512> {
+        allocations
+            .iter()
+            .try_fold(U512::zero(), |total, allocation| {
+                total.checked_add(*allocation.amount())
+            })
+    }
+
+    /// Returns the sum of the amounts of `allocations`, saturating at [`U512::max_value`] rather
+    /// than overflowing.
+    pub fn saturating_total(allocations: &[SeigniorageAllocation]) -> U512 {
+        allocations.iter().fold(U512::zero(), |total, allocation| {
+            total.saturating_add(*allocation.amount())
+        })
+    }
+
+    /// Deserializes a `SeigniorageAllocation` from the front of `bytes`, returning it along with
+    /// the number of bytes consumed.
+    pub fn from_bytes_with_len(bytes: &[u8]) -> Result<(Self, usize), bytesrepr::Error> {
+        let (seigniorage_allocation, remainder) = SeigniorageAllocation::from_bytes(bytes)?;
+        Ok((seigniorage_allocation, bytes.len() - remainder.len()))
+    }
+
+    /// Returns the public key of the recipient of the allocation, i.e. the validator for a
+    /// validator allocation or the delegator for a delegator allocation.
+    fn recipient_public_key(&self) -> &PublicKey {
+        match self {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                ..
+            } => validator_public_key,
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                ..
+            } => delegator_public_key,
+        }
+    }
+
+    /// Returns the validator's public key, plus the delegator's public key for a delegator
+    /// allocation, which together identify the recipient independently of the amount.
+    fn recipient_id(&self) -> (&PublicKey, Option<&PublicKey>) {
+        match self {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                ..
+            } => (validator_public_key, None),
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                ..
+            } => (validator_public_key, Some(delegator_public_key)),
+        }
+    }
+
     fn tag(&self) -> u8 {
         match self {
             SeigniorageAllocation::Validator { .. } => SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG,
@@ -151,14 +277,83 @@ impl FromBytes for SeigniorageAllocation {
     }
 }
 
+impl TryFrom<&[u8]> for SeigniorageAllocation {
+    type Error = bytesrepr::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let (seigniorage_allocation, remainder) = SeigniorageAllocation::from_bytes(bytes)?;
+        if !remainder.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        Ok(seigniorage_allocation)
+    }
+}
+
+// `CLType` has no way to describe a sum type whose variants have different fields, and the bytes of
+// a `SeigniorageAllocation` are a tag followed by the fields of the tagged variant.  A tuple type
+// would misdescribe one of the two layouts, so `Any` is the only accurate choice.
 impl CLTyped for SeigniorageAllocation {
     fn cl_type() -> CLType {
         CLType::Any
     }
 }
 
+/// The kind of a [`JsonSeigniorageAllocation`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum JsonSeigniorageAllocationKind {
+    /// An allocation for a validator.
+    Validator,
+    /// An allocation for a delegator.
+    Delegator,
+}
+
+/// A flattened representation of a [`SeigniorageAllocation`], with the same fields for both
+/// kinds of allocation.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonSeigniorageAllocation {
+    /// The kind of allocation.
+    pub kind: JsonSeigniorageAllocationKind,
+    /// The validator's public key.
+    pub validator: PublicKey,
+    /// The delegator's public key, for a delegator allocation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegator: Option<PublicKey>,
+    /// The allocated amount.
+    pub amount: U512,
+}
+
+#[cfg(feature = "std")]
+impl From<&SeigniorageAllocation> for JsonSeigniorageAllocation {
+    fn from(allocation: &SeigniorageAllocation) -> Self {
+        match allocation {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+            } => JsonSeigniorageAllocation {
+                kind: JsonSeigniorageAllocationKind::Validator,
+                validator: validator_public_key.clone(),
+                delegator: None,
+                amount: *amount,
+            },
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => JsonSeigniorageAllocation {
+                kind: JsonSeigniorageAllocationKind::Delegator,
+                validator: validator_public_key.clone(),
+                delegator: Some(delegator_public_key.clone()),
+                amount: *amount,
+            },
+        }
+    }
+}
+
 /// Auction metadata.  Intended to be recorded at each era.
-#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct EraInfo {
@@ -174,6 +369,40 @@ impl EraInfo {
         }
     }
 
+    /// Constructs an [`EraInfo`] from a bare JSON array of seigniorage allocations.
+    ///
+    /// This differs from deserializing an `EraInfo` directly, which expects a JSON object holding
+    /// the array in its `seigniorage_allocations` field.
+    pub fn from_allocations_json(json: &str) -> Result<Self, serde_json::Error> {
+        let seigniorage_allocations = serde_json::from_str(json)?;
+        Ok(EraInfo {
+            seigniorage_allocations,
+        })
+    }
+
+    /// Returns the `EraInfo` as pretty-printed JSON, e.g. for display by command-line tools.
+    #[cfg(feature = "std")]
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serializes the `EraInfo` using bincode, for off-chain consumers which already use it.
+    ///
+    /// This is not the canonical encoding: `EraInfo`s are stored in global state and hashed in
+    /// their [`bytesrepr`] form, and the two forms are not interchangeable.
+    ///
+    /// Only available with both the `std` and `bincode` features enabled.
+    #[cfg(all(feature = "std", feature = "bincode"))]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Deserializes an `EraInfo` written by [`EraInfo::to_bincode`].
+    #[cfg(all(feature = "std", feature = "bincode"))]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+
     /// Returns a reference to the seigniorage allocations collection
     pub fn seigniorage_allocations(&self) -> &Vec<SeigniorageAllocation> {
         &self.seigniorage_allocations
@@ -184,6 +413,16 @@ impl EraInfo {
         &mut self.seigniorage_allocations
     }
 
+    /// Returns the number of seigniorage allocations.
+    pub fn len(&self) -> usize {
+        self.seigniorage_allocations.len()
+    }
+
+    /// Returns `true` if there are no seigniorage allocations.
+    pub fn is_empty(&self) -> bool {
+        self.seigniorage_allocations.is_empty()
+    }
+
     /// Returns all seigniorage allocations that match the provided public key
     /// using the following criteria:
     /// * If the match candidate is a validator allocation, the provided public key is matched
@@ -204,6 +443,435 @@ impl EraInfo {
                 } => public_key == *delegator_public_key,
             })
     }
+
+    /// Returns `true` if any seigniorage allocation matches the provided public key, using the
+    /// same criteria as [`EraInfo::select`].
+    pub fn contains_recipient(&self, public_key: &PublicKey) -> bool {
+        self.seigniorage_allocations
+            .iter()
+            .any(|allocation| allocation.recipient_public_key() == public_key)
+    }
+
+    /// Returns the distinct public keys receiving seigniorage allocations.
+    ///
+    /// If `include_delegated_validators` is `true`, the validator public keys of delegator
+    /// allocations are included too, even for validators without an allocation of their own.
+    pub fn recipients(&self, include_delegated_validators: bool) -> BTreeSet<PublicKey> {
+        let mut recipients = BTreeSet::new();
+        for allocation in &self.seigniorage_allocations {
+            match allocation {
+                SeigniorageAllocation::Validator {
+                    validator_public_key,
+                    ..
+                } => {
+                    recipients.insert(validator_public_key.clone());
+                }
+                SeigniorageAllocation::Delegator {
+                    delegator_public_key,
+                    validator_public_key,
+                    ..
+                } => {
+                    recipients.insert(delegator_public_key.clone());
+                    if include_delegated_validators {
+                        recipients.insert(validator_public_key.clone());
+                    }
+                }
+            }
+        }
+        recipients
+    }
+
+    /// Returns the recipient and amount of each seigniorage allocation, with the amount divided by
+    /// `divisor`, e.g. to convert from motes to a larger denomination.
+    ///
+    /// The recipient is the validator for a validator allocation or the delegator for a delegator
+    /// allocation. Returns `None` if `divisor` is zero.
+    pub fn allocations_scaled(&self, divisor: U512) -> Option<Vec<(PublicKey, U512)>> {
+        self.seigniorage_allocations
+            .iter()
+            .map(|allocation| {
+                let scaled_amount = allocation.amount().checked_div(divisor)?;
+                Some((allocation.recipient_public_key().clone(), scaled_amount))
+            })
+            .collect()
+    }
+
+    /// Returns the `n` seigniorage allocations with the largest amounts, largest first.
+    ///
+    /// Allocations with equal amounts are ordered as by [`Ord`]. Only the returned allocations are
+    /// fully sorted.
+    pub fn top_n(&self, n: usize) -> Vec<&SeigniorageAllocation> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let by_amount_descending = |lhs: &&SeigniorageAllocation, rhs: &&SeigniorageAllocation| {
+            rhs.amount().cmp(lhs.amount()).then_with(|| lhs.cmp(rhs))
+        };
+        let mut allocations: Vec<&SeigniorageAllocation> =
+            self.seigniorage_allocations.iter().collect();
+        if n < allocations.len() {
+            allocations.select_nth_unstable_by(n - 1, by_amount_descending);
+            allocations.truncate(n);
+        }
+        allocations.sort_unstable_by(by_amount_descending);
+        allocations
+    }
+
+    /// Counts the seigniorage allocations by amount, using `buckets` as the ascending lower edges
+    /// of the buckets.
+    ///
+    /// The returned `Vec` has one more entry than `buckets`: entry `0` counts the amounts below
+    /// `buckets[0]`, entry `i` counts the amounts at least `buckets[i - 1]` and below
+    /// `buckets[i]`, and the last entry counts the amounts at least the last edge.
+    pub fn amount_histogram(&self, buckets: &[U512]) -> Vec<usize> {
+        let mut counts = vec![0; buckets.len() + 1];
+        for allocation in &self.seigniorage_allocations {
+            let bucket = buckets.partition_point(|edge| edge <= allocation.amount());
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    /// Returns the number of bytes the `EraInfo` occupies when serialized, without serializing it.
+    pub fn serialized_size(&self) -> usize {
+        self.serialized_length()
+    }
+
+    /// Returns `true` if the serialized `EraInfo` occupies no more than `max` bytes.
+    pub fn fits_within(&self, max: usize) -> bool {
+        self.serialized_size() <= max
+    }
+
+    /// Deserializes an `EraInfo` from `bytes`, rejecting it with [`bytesrepr::Error::Formatting`]
+    /// if its length prefix claims more than `max_allocations` seigniorage allocations.
+    ///
+    /// The length prefix is checked before any allocation is made, so this should be preferred
+    /// over [`FromBytes::from_bytes`] when `bytes` come from an untrusted source.
+    pub fn from_bytes_bounded(
+        bytes: &[u8],
+        max_allocations: u32,
+    ) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (count, mut remainder) = u32::from_bytes(bytes)?;
+        if count > max_allocations {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let mut seigniorage_allocations = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let (allocation, rem) = SeigniorageAllocation::from_bytes(remainder)?;
+            seigniorage_allocations.push(allocation);
+            remainder = rem;
+        }
+        Ok((
+            EraInfo {
+                seigniorage_allocations,
+            },
+            remainder,
+        ))
+    }
+
+    /// Appends the serialized `EraInfo` to `out`, preceded by its length in bytes as a `u32`.
+    ///
+    /// Framed `EraInfo`s can be concatenated and read back in order with
+    /// [`EraInfo::read_framed`].
+    pub fn write_framed(&self, out: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        let mut bytes = self.to_bytes()?;
+        let frame_length = u32::try_from(bytes.len()).map_err(|_| bytesrepr::Error::OutOfMemory)?;
+        out.append(&mut frame_length.to_bytes()?);
+        out.append(&mut bytes);
+        Ok(())
+    }
+
+    /// Reads an `EraInfo` written by [`EraInfo::write_framed`] from the front of `bytes`,
+    /// returning it along with the bytes following its frame.
+    ///
+    /// Returns [`bytesrepr::Error::LeftOverBytes`] if the frame holds more than one `EraInfo`.
+    pub fn read_framed(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (frame_length, remainder) = u32::from_bytes(bytes)?;
+        let (frame, remainder) = bytesrepr::safe_split_at(remainder, frame_length as usize)?;
+        let era_info = EraInfo::try_from(frame)?;
+        Ok((era_info, remainder))
+    }
+
+    /// Sorts the seigniorage allocations into their canonical order: validator allocations before
+    /// delegator allocations, then by public key(s), then by amount.
+    ///
+    /// Note that this changes the serialized form of the `EraInfo`, so when comparing the hashes
+    /// of `EraInfo`s assembled from different sources, both sides should be sorted first.
+    pub fn sort(&mut self) {
+        self.seigniorage_allocations.sort_unstable();
+    }
+
+    /// Retains only the seigniorage allocations for which `f` returns `true`, preserving their
+    /// order.
+    pub fn retain<F: FnMut(&SeigniorageAllocation) -> bool>(&mut self, f: F) {
+        self.seigniorage_allocations.retain(f)
+    }
+
+    /// Returns a new `EraInfo` holding clones of only the seigniorage allocations with an amount
+    /// of at least `min`, preserving their order.
+    pub fn above_threshold(&self, min: U512) -> EraInfo {
+        let seigniorage_allocations = self
+            .seigniorage_allocations
+            .iter()
+            .filter(|allocation| *allocation.amount() >= min)
+            .cloned()
+            .collect();
+        EraInfo {
+            seigniorage_allocations,
+        }
+    }
+
+    /// Consumes `self`, splitting it into an `EraInfo` holding only the validator allocations and
+    /// one holding only the delegator allocations, each in their original relative order.
+    pub fn partition(self) -> (EraInfo, EraInfo) {
+        let (validator_allocations, delegator_allocations) = self
+            .seigniorage_allocations
+            .into_iter()
+            .partition(SeigniorageAllocation::is_validator);
+        (
+            EraInfo {
+                seigniorage_allocations: validator_allocations,
+            },
+            EraInfo {
+                seigniorage_allocations: delegator_allocations,
+            },
+        )
+    }
+
+    /// Combines the seigniorage allocations of all `infos` into a single `EraInfo`, in the order
+    /// they are encountered.
+    ///
+    /// With [`EraInfoAggregation::Merge`], allocations to the same recipient as per
+    /// [`SeigniorageAllocation::same_recipient`] are combined into the first of them, with the
+    /// amounts summed, saturating at [`U512::max_value`].
+    pub fn aggregate<I: IntoIterator<Item = EraInfo>>(
+        infos: I,
+        aggregation: EraInfoAggregation,
+    ) -> EraInfo {
+        let allocations = infos
+            .into_iter()
+            .flat_map(|info| info.seigniorage_allocations);
+        let seigniorage_allocations = match aggregation {
+            EraInfoAggregation::Concat => allocations.collect(),
+            EraInfoAggregation::Merge => {
+                let mut merged: Vec<SeigniorageAllocation> = Vec::new();
+                let mut positions: BTreeMap<(PublicKey, Option<PublicKey>), usize> =
+                    BTreeMap::new();
+                for allocation in allocations {
+                    let (validator_public_key, delegator_public_key) = allocation.recipient_id();
+                    let recipient_id =
+                        (validator_public_key.clone(), delegator_public_key.cloned());
+                    match positions.get(&recipient_id) {
+                        Some(position) => {
+                            let amount = merged[*position].amount_mut();
+                            *amount = amount.saturating_add(*allocation.amount());
+                        }
+                        None => {
+                            positions.insert(recipient_id, merged.len());
+                            merged.push(allocation);
+                        }
+                    }
+                }
+                merged
+            }
+        };
+        EraInfo {
+            seigniorage_allocations,
+        }
+    }
+
+    /// Collapses all seigniorage allocations to the same recipient as per
+    /// [`SeigniorageAllocation::same_recipient`] into the first of them, summing their amounts.
+    ///
+    /// A sum which would overflow is capped at [`U512::max_value`], as with
+    /// [`EraInfo::aggregate`] in [`EraInfoAggregation::Merge`] mode.
+    pub fn merge_duplicates(&mut self) {
+        let era_info = EraInfo {
+            seigniorage_allocations: mem::take(&mut self.seigniorage_allocations),
+        };
+        *self = EraInfo::aggregate(iter::once(era_info), EraInfoAggregation::Merge);
+    }
+
+    /// Compares `self` against a `previous` `EraInfo`, matching allocations by their recipient as
+    /// per [`SeigniorageAllocation::same_recipient`].
+    ///
+    /// Each recipient is expected to appear at most once in each `EraInfo`, as is the case for
+    /// those recorded by the auction.
+    pub fn diff(&self, previous: &EraInfo) -> EraInfoDiff {
+        let previous_by_recipient: BTreeMap<_, _> = previous
+            .seigniorage_allocations
+            .iter()
+            .map(|allocation| (allocation.recipient_id(), allocation))
+            .collect();
+        let current_recipients: BTreeSet<_> = self
+            .seigniorage_allocations
+            .iter()
+            .map(SeigniorageAllocation::recipient_id)
+            .collect();
+
+        let mut diff = EraInfoDiff::default();
+        for allocation in &self.seigniorage_allocations {
+            match previous_by_recipient.get(&allocation.recipient_id()) {
+                None => diff.added.push(allocation.clone()),
+                Some(previous_allocation)
+                    if previous_allocation.amount() != allocation.amount() =>
+                {
+                    diff.changed
+                        .push(((*previous_allocation).clone(), allocation.clone()))
+                }
+                Some(_) => (),
+            }
+        }
+        diff.removed = previous
+            .seigniorage_allocations
+            .iter()
+            .filter(|allocation| !current_recipients.contains(&allocation.recipient_id()))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Builds an index of the seigniorage allocations by public key, allowing repeated calls to
+    /// [`EraInfoIndex::select`] without scanning the whole collection each time.
+    pub fn build_index(&self) -> EraInfoIndex {
+        let mut positions: BTreeMap<PublicKey, Vec<usize>> = BTreeMap::new();
+        for (position, allocation) in self.seigniorage_allocations.iter().enumerate() {
+            positions
+                .entry(allocation.recipient_public_key().clone())
+                .or_default()
+                .push(position);
+        }
+        EraInfoIndex {
+            era_info: self,
+            positions,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a EraInfo {
+    type Item = &'a SeigniorageAllocation;
+    type IntoIter = Iter<'a, SeigniorageAllocation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.seigniorage_allocations.iter()
+    }
+}
+
+/// An iterator deserializing the seigniorage allocations of a serialized `Vec` of
+/// [`SeigniorageAllocation`]s, such as a serialized [`EraInfo`], one at a time.
+///
+/// After yielding an error, the reader yields nothing further.
+#[derive(Debug, Clone)]
+pub struct SeigniorageAllocationReader<'a> {
+    bytes: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> SeigniorageAllocationReader<'a> {
+    /// Constructs a reader over `bytes`, consuming the length prefix of the `Vec`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, bytesrepr::Error> {
+        let (remaining, bytes) = u32::from_bytes(bytes)?;
+        Ok(SeigniorageAllocationReader { bytes, remaining })
+    }
+
+    /// Returns the bytes not yet consumed by the reader.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for SeigniorageAllocationReader<'a> {
+    type Item = Result<SeigniorageAllocation, bytesrepr::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        match SeigniorageAllocation::from_bytes(self.bytes) {
+            Ok((allocation, remainder)) => {
+                self.bytes = remainder;
+                self.remaining -= 1;
+                Some(Ok(allocation))
+            }
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+/// An index of the seigniorage allocations of an [`EraInfo`] by public key.
+///
+/// Lookups take O(log n) time in the number of distinct public keys rather than the O(1) of a
+/// hash map. A `BTreeMap` is used as it is available without `std`, like the rest of this crate's
+/// collections, and iterates in a deterministic order.
+#[derive(Debug, Clone)]
+pub struct EraInfoIndex<'a> {
+    era_info: &'a EraInfo,
+    positions: BTreeMap<PublicKey, Vec<usize>>,
+}
+
+impl<'a> EraInfoIndex<'a> {
+    /// Returns all seigniorage allocations that match the provided public key, using the same
+    /// criteria as [`EraInfo::select`].
+    pub fn select(
+        &self,
+        public_key: &PublicKey,
+    ) -> impl Iterator<Item = &'a SeigniorageAllocation> + '_ {
+        let era_info: &'a EraInfo = self.era_info;
+        let allocations = &era_info.seigniorage_allocations;
+        self.positions
+            .get(public_key)
+            .map(|positions| positions.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(move |position| &allocations[*position])
+    }
+}
+
+/// How [`EraInfo::aggregate`] combines the seigniorage allocations of several `EraInfo`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EraInfoAggregation {
+    /// Keep every allocation.
+    Concat,
+    /// Sum the amounts of allocations to the same recipient.
+    Merge,
+}
+
+/// The differences between two [`EraInfo`]s, as returned by [`EraInfo::diff`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EraInfoDiff {
+    added: Vec<SeigniorageAllocation>,
+    removed: Vec<SeigniorageAllocation>,
+    changed: Vec<(SeigniorageAllocation, SeigniorageAllocation)>,
+}
+
+impl EraInfoDiff {
+    /// Returns the allocations whose recipient had no allocation in the previous `EraInfo`.
+    pub fn added(&self) -> &[SeigniorageAllocation] {
+        &self.added
+    }
+
+    /// Returns the previous allocations whose recipient has no allocation in the current
+    /// `EraInfo`.
+    pub fn removed(&self) -> &[SeigniorageAllocation] {
+        &self.removed
+    }
+
+    /// Returns the allocations whose amount changed, as `(previous, current)` pairs.
+    pub fn changed(&self) -> &[(SeigniorageAllocation, SeigniorageAllocation)] {
+        &self.changed
+    }
+
+    /// Returns `true` if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
 }
 
 impl ToBytes for EraInfo {
@@ -228,13 +896,77 @@ impl FromBytes for EraInfo {
     }
 }
 
+impl TryFrom<&[u8]> for EraInfo {
+    type Error = bytesrepr::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let (era_info, remainder) = EraInfo::from_bytes(bytes)?;
+        if !remainder.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        Ok(era_info)
+    }
+}
+
 impl CLTyped for EraInfo {
     fn cl_type() -> CLType {
         CLType::List(Box::new(SeigniorageAllocation::cl_type()))
     }
 }
 
-/// Generators for [`SeigniorageAllocation`] and [`EraInfo`]
+/// An [`EraInfo`] together with the id of the era it was recorded for.
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct EraAuctionInfo {
+    era_id: EraId,
+    info: EraInfo,
+}
+
+impl EraAuctionInfo {
+    /// Constructs an [`EraAuctionInfo`].
+    pub fn new(era_id: EraId, info: EraInfo) -> Self {
+        EraAuctionInfo { era_id, info }
+    }
+
+    /// Returns the id of the era the info was recorded for.
+    pub fn era_id(&self) -> EraId {
+        self.era_id
+    }
+
+    /// Returns the era info.
+    pub fn info(&self) -> &EraInfo {
+        &self.info
+    }
+
+    /// Consumes `self`, returning the era id and the era info.
+    pub fn into_parts(self) -> (EraId, EraInfo) {
+        (self.era_id, self.info)
+    }
+}
+
+impl ToBytes for EraAuctionInfo {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.append(&mut self.era_id.to_bytes()?);
+        buffer.append(&mut self.info.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.era_id.serialized_length() + self.info.serialized_length()
+    }
+}
+
+impl FromBytes for EraAuctionInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (era_id, rem) = EraId::from_bytes(bytes)?;
+        let (info, rem) = EraInfo::from_bytes(rem)?;
+        Ok((EraAuctionInfo { era_id, info }, rem))
+    }
+}
+
+/// Generators for [`SeigniorageAllocation`], [`EraInfo`] and [`EraAuctionInfo`]
 #[cfg(any(feature = "gens", test))]
 pub mod gens {
     use proptest::{
@@ -245,8 +977,8 @@ pub mod gens {
 
     use crate::{
         crypto::gens::public_key_arb,
-        gens::u512_arb,
-        system::auction::{EraInfo, SeigniorageAllocation},
+        gens::{era_id_arb, u512_arb},
+        system::auction::{EraAuctionInfo, EraInfo, SeigniorageAllocation},
     };
 
     fn seigniorage_allocation_validator_arb() -> impl Strategy<Value = SeigniorageAllocation> {
@@ -279,20 +1011,746 @@ pub mod gens {
             era_info
         })
     }
+
+    /// Creates an arbitrary [`EraAuctionInfo`]
+    pub fn era_auction_info_arb(
+        size: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = EraAuctionInfo> {
+        (era_id_arb(), era_info_arb(size))
+            .prop_map(|(era_id, era_info)| EraAuctionInfo::new(era_id, era_info))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::{boxed::Box, vec::Vec};
+    use core::convert::TryFrom;
+
     use proptest::prelude::*;
 
-    use crate::bytesrepr;
+    use crate::{
+        bytesrepr::{self, FromBytes, ToBytes},
+        crypto::gens::public_key_arb,
+        system::auction::Error,
+        CLType, CLTyped, CLValue, PublicKey, SecretKey, U512,
+    };
+
+    use super::{
+        gens, EraInfo, EraInfoAggregation, SeigniorageAllocation, SeigniorageAllocationReader,
+    };
 
-    use super::gens;
+    fn public_key(seed: u8) -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap())
+    }
 
     proptest! {
         #[test]
         fn test_serialization_roundtrip(era_info in gens::era_info_arb(0..32)) {
             bytesrepr::test_serialization_roundtrip(&era_info)
         }
+
+        #[cfg(all(feature = "std", feature = "bincode"))]
+        #[test]
+        fn test_bincode_roundtrip(era_info in gens::era_info_arb(0..32)) {
+            let bincode = era_info.to_bincode().unwrap();
+            assert_eq!(EraInfo::from_bincode(&bincode).unwrap(), era_info);
+        }
+
+        #[test]
+        fn should_stream_allocations(era_info in gens::era_info_arb(0..32)) {
+            let bytes = era_info.to_bytes().unwrap();
+
+            let mut reader = SeigniorageAllocationReader::new(&bytes).unwrap();
+            let streamed: Vec<SeigniorageAllocation> =
+                reader.by_ref().collect::<Result<_, _>>().unwrap();
+            let (expected, _) = Vec::<SeigniorageAllocation>::from_bytes(&bytes).unwrap();
+            assert_eq!(streamed, expected);
+            assert!(reader.remainder().is_empty());
+        }
+
+        #[test]
+        fn should_report_consumed_length(
+            seigniorage_allocation in gens::seigniorage_allocation_arb(),
+            trailing_bytes in proptest::collection::vec(any::<u8>(), 0..8),
+        ) {
+            let mut bytes = seigniorage_allocation.to_bytes().unwrap();
+            bytes.extend_from_slice(&trailing_bytes);
+
+            let (parsed, consumed) = SeigniorageAllocation::from_bytes_with_len(&bytes).unwrap();
+            assert_eq!(parsed, seigniorage_allocation);
+            assert_eq!(consumed, seigniorage_allocation.serialized_length());
+        }
+
+        #[test]
+        fn should_read_framed_era_infos_in_order(
+            first in gens::era_info_arb(0..8),
+            second in gens::era_info_arb(0..8),
+            third in gens::era_info_arb(0..8),
+        ) {
+            let mut bytes = Vec::new();
+            first.write_framed(&mut bytes).unwrap();
+            second.write_framed(&mut bytes).unwrap();
+            third.write_framed(&mut bytes).unwrap();
+
+            let (read_first, remainder) = EraInfo::read_framed(&bytes).unwrap();
+            let (read_second, remainder) = EraInfo::read_framed(remainder).unwrap();
+            let (read_third, remainder) = EraInfo::read_framed(remainder).unwrap();
+            assert_eq!(read_first, first);
+            assert_eq!(read_second, second);
+            assert_eq!(read_third, third);
+            assert!(remainder.is_empty());
+            assert_eq!(
+                EraInfo::read_framed(remainder).unwrap_err(),
+                bytesrepr::Error::EarlyEndOfStream
+            );
+        }
+
+        #[test]
+        fn test_era_auction_info_serialization_roundtrip(
+            era_auction_info in gens::era_auction_info_arb(0..32)
+        ) {
+            bytesrepr::test_serialization_roundtrip(&era_auction_info)
+        }
+
+        #[test]
+        fn indexed_select_should_match_linear_select(
+            era_info in gens::era_info_arb(0..32),
+            other_public_key in public_key_arb()
+        ) {
+            let index = era_info.build_index();
+            let public_keys = era_info
+                .seigniorage_allocations()
+                .iter()
+                .map(|allocation| allocation.recipient_public_key().clone())
+                .chain(Some(other_public_key));
+            for public_key in public_keys {
+                let indexed: Vec<_> = index.select(&public_key).collect();
+                let linear: Vec<_> = era_info.select(public_key).collect();
+                prop_assert_eq!(indexed, linear);
+            }
+        }
+
+        #[test]
+        fn contains_recipient_should_match_select(
+            era_info in gens::era_info_arb(0..32),
+            other_public_key in public_key_arb()
+        ) {
+            let public_keys = era_info
+                .seigniorage_allocations()
+                .iter()
+                .map(|allocation| allocation.recipient_public_key().clone())
+                .chain(Some(other_public_key));
+            for public_key in public_keys {
+                prop_assert_eq!(
+                    era_info.contains_recipient(&public_key),
+                    era_info.select(public_key.clone()).next().is_some()
+                );
+            }
+        }
+
+        #[test]
+        fn sorted_era_infos_should_serialize_identically(era_info in gens::era_info_arb(0..32)) {
+            let mut reversed = era_info.clone();
+            reversed.seigniorage_allocations_mut().reverse();
+
+            let mut sorted = era_info;
+            sorted.sort();
+            reversed.sort();
+
+            prop_assert_eq!(sorted.to_bytes().unwrap(), reversed.to_bytes().unwrap());
+        }
+
+        #[test]
+        fn length_matches_bytes(
+            allocation in gens::seigniorage_allocation_arb(),
+            era_info in gens::era_info_arb(0..32),
+            era_auction_info in gens::era_auction_info_arb(0..32)
+        ) {
+            prop_assert_eq!(allocation.serialized_length(), allocation.to_bytes().unwrap().len());
+            prop_assert_eq!(era_info.serialized_length(), era_info.to_bytes().unwrap().len());
+            prop_assert_eq!(
+                era_auction_info.serialized_length(),
+                era_auction_info.to_bytes().unwrap().len()
+            );
+        }
+
+        #[test]
+        fn serialized_size_should_match_bytes(era_info in gens::era_info_arb(0..32)) {
+            let serialized_length = era_info.to_bytes().unwrap().len();
+            prop_assert_eq!(era_info.serialized_size(), serialized_length);
+            prop_assert!(era_info.fits_within(serialized_length));
+            prop_assert!(!era_info.fits_within(serialized_length - 1));
+        }
+
+        #[test]
+        fn should_parse_exact_bytes(era_info in gens::era_info_arb(0..32)) {
+            let bytes = era_info.to_bytes().unwrap();
+            prop_assert_eq!(EraInfo::try_from(bytes.as_slice()), Ok(era_info.clone()));
+
+            for allocation in era_info.seigniorage_allocations() {
+                let bytes = allocation.to_bytes().unwrap();
+                prop_assert_eq!(
+                    SeigniorageAllocation::try_from(bytes.as_slice()),
+                    Ok(allocation.clone())
+                );
+            }
+        }
+
+        #[test]
+        fn should_fail_to_parse_trailing_bytes(
+            era_info in gens::era_info_arb(0..32),
+            allocation in gens::seigniorage_allocation_arb()
+        ) {
+            let mut bytes = era_info.to_bytes().unwrap();
+            bytes.push(0);
+            prop_assert_eq!(
+                EraInfo::try_from(bytes.as_slice()),
+                Err(bytesrepr::Error::LeftOverBytes)
+            );
+
+            let mut bytes = allocation.to_bytes().unwrap();
+            bytes.push(0);
+            prop_assert_eq!(
+                SeigniorageAllocation::try_from(bytes.as_slice()),
+                Err(bytesrepr::Error::LeftOverBytes)
+            );
+        }
+
+        #[test]
+        fn should_parse_within_bound(era_info in gens::era_info_arb(0..32)) {
+            let bytes = era_info.to_bytes().unwrap();
+            let count = era_info.seigniorage_allocations().len() as u32;
+            let (parsed, remainder) = EraInfo::from_bytes_bounded(&bytes, count).unwrap();
+            prop_assert_eq!(parsed, era_info);
+            prop_assert!(remainder.is_empty());
+        }
+
+        #[test]
+        fn should_partition_by_variant(era_info in gens::era_info_arb(0..32)) {
+            let original_len = era_info.seigniorage_allocations().len();
+            let (validators, delegators) = era_info.partition();
+            prop_assert_eq!(
+                validators.seigniorage_allocations().len()
+                    + delegators.seigniorage_allocations().len(),
+                original_len
+            );
+            prop_assert!(validators
+                .seigniorage_allocations()
+                .iter()
+                .all(SeigniorageAllocation::is_validator));
+            prop_assert!(delegators
+                .seigniorage_allocations()
+                .iter()
+                .all(SeigniorageAllocation::is_delegator));
+        }
+
+        #[test]
+        fn should_write_amount_bytes(
+            allocations in proptest::collection::vec(gens::seigniorage_allocation_arb(), 0..8)
+        ) {
+            let mut buffer = Vec::new();
+            let mut expected = Vec::new();
+            for allocation in &allocations {
+                allocation.amount_write_bytes(&mut buffer);
+                expected.extend(allocation.amount().to_bytes().unwrap());
+            }
+            prop_assert_eq!(buffer, expected);
+        }
+
+        #[test]
+        fn should_roundtrip_through_cl_value(allocation in gens::seigniorage_allocation_arb()) {
+            let cl_value = CLValue::from_t(allocation.clone()).unwrap();
+            prop_assert_eq!(cl_value.cl_type(), &SeigniorageAllocation::cl_type());
+            prop_assert_eq!(cl_value.into_t::<SeigniorageAllocation>().unwrap(), allocation);
+        }
+
+        #[test]
+        fn should_iterate_over_allocations(era_info in gens::era_info_arb(0..32)) {
+            let mut iterated = Vec::new();
+            for allocation in &era_info {
+                iterated.push(allocation);
+            }
+            let expected: Vec<_> = era_info.seigniorage_allocations().iter().collect();
+            prop_assert_eq!(iterated, expected);
+        }
+    }
+
+    #[test]
+    fn should_parse_bare_json_array_of_allocations() {
+        let allocations = vec![
+            SeigniorageAllocation::validator(public_key(1), U512::from(2)),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3)),
+        ];
+        let json = serde_json::to_string(&allocations).unwrap();
+        assert!(json.starts_with('['));
+
+        let era_info = EraInfo::from_allocations_json(&json).unwrap();
+        assert_eq!(era_info.seigniorage_allocations(), &allocations);
+        assert!(serde_json::from_str::<EraInfo>(&json).is_err());
+    }
+
+    #[test]
+    fn checked_total_should_sum_amounts() {
+        let allocations = [
+            SeigniorageAllocation::validator(public_key(1), U512::from(2)),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3)),
+        ];
+        assert_eq!(
+            SeigniorageAllocation::checked_total(&allocations),
+            Some(U512::from(5))
+        );
+        assert_eq!(
+            SeigniorageAllocation::checked_total(&[]),
+            Some(U512::zero())
+        );
+    }
+
+    #[test]
+    fn checked_total_should_detect_overflow() {
+        let allocations = [
+            SeigniorageAllocation::validator(public_key(1), U512::max_value() - 1),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+        ];
+        assert_eq!(SeigniorageAllocation::checked_total(&allocations), None);
+    }
+
+    #[test]
+    fn should_retain_allocations_above_threshold() {
+        let mut era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(1)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(10)),
+                SeigniorageAllocation::validator(public_key(3), U512::from(5)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(3), U512::from(20)),
+            ],
+        };
+        let dust_threshold = U512::from(5);
+
+        era_info.retain(|allocation| *allocation.amount() > dust_threshold);
+
+        assert_eq!(
+            era_info.seigniorage_allocations(),
+            &vec![
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(3), U512::from(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_match_same_recipient_regardless_of_amount() {
+        let validator = SeigniorageAllocation::validator(public_key(1), U512::from(2));
+        let drifted_validator = SeigniorageAllocation::validator(public_key(1), U512::from(3));
+        assert_ne!(validator, drifted_validator);
+        assert!(validator.same_recipient(&drifted_validator));
+
+        let delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2));
+        let drifted_delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3));
+        assert!(delegator.same_recipient(&drifted_delegator));
+
+        let other_validator = SeigniorageAllocation::validator(public_key(2), U512::from(2));
+        assert!(!validator.same_recipient(&other_validator));
+        let other_delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(3), U512::from(2));
+        assert!(!delegator.same_recipient(&other_delegator));
+        // A validator allocation never matches a delegator allocation, even for the same key.
+        let self_delegator =
+            SeigniorageAllocation::delegator(public_key(1), public_key(1), U512::from(2));
+        assert!(!validator.same_recipient(&self_delegator));
+    }
+
+    #[test]
+    fn should_return_largest_allocations_first() {
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(5)),
+                SeigniorageAllocation::validator(public_key(2), U512::from(50)),
+                SeigniorageAllocation::delegator(public_key(3), public_key(1), U512::from(1)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(2), U512::from(20)),
+                SeigniorageAllocation::delegator(public_key(5), public_key(2), U512::from(10)),
+            ],
+        };
+        let amounts = |allocations: Vec<&SeigniorageAllocation>| -> Vec<U512> {
+            allocations
+                .into_iter()
+                .map(|allocation| *allocation.amount())
+                .collect()
+        };
+
+        assert_eq!(
+            amounts(era_info.top_n(3)),
+            vec![U512::from(50), U512::from(20), U512::from(10)]
+        );
+        assert_eq!(amounts(era_info.top_n(10)).len(), 5);
+        assert_eq!(amounts(era_info.top_n(10))[4], U512::from(1));
+        assert!(era_info.top_n(0).is_empty());
+    }
+
+    #[test]
+    fn should_scale_allocation_amounts() {
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(2_500_000_000u64)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(999)),
+            ],
+        };
+        let motes_per_cspr = U512::from(1_000_000_000);
+
+        assert_eq!(
+            era_info.allocations_scaled(motes_per_cspr),
+            Some(vec![
+                (public_key(1), U512::from(2)),
+                (public_key(2), U512::zero())
+            ])
+        );
+        assert_eq!(era_info.allocations_scaled(U512::zero()), None);
+    }
+
+    #[test]
+    fn should_collect_distinct_recipients() {
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(1)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(3), U512::from(3)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(3), U512::from(4)),
+            ],
+        };
+
+        let recipients: Vec<PublicKey> = era_info.recipients(false).into_iter().collect();
+        let mut expected = vec![public_key(1), public_key(2), public_key(4)];
+        expected.sort();
+        assert_eq!(recipients, expected);
+
+        let recipients: Vec<PublicKey> = era_info.recipients(true).into_iter().collect();
+        let mut expected = vec![public_key(1), public_key(2), public_key(3), public_key(4)];
+        expected.sort();
+        assert_eq!(recipients, expected);
+        for allocation in era_info.seigniorage_allocations() {
+            assert!(recipients.contains(allocation.recipient_public_key()));
+        }
+    }
+
+    #[test]
+    fn should_reject_zero_amount_allocations() {
+        assert_eq!(
+            SeigniorageAllocation::try_validator(public_key(1), U512::zero()),
+            Err(Error::InvalidAmount)
+        );
+        assert_eq!(
+            SeigniorageAllocation::try_delegator(public_key(2), public_key(1), U512::zero()),
+            Err(Error::InvalidAmount)
+        );
+
+        assert_eq!(
+            SeigniorageAllocation::try_validator(public_key(1), U512::one()),
+            Ok(SeigniorageAllocation::validator(public_key(1), U512::one()))
+        );
+        assert_eq!(
+            SeigniorageAllocation::try_delegator(public_key(2), public_key(1), U512::one()),
+            Ok(SeigniorageAllocation::delegator(
+                public_key(2),
+                public_key(1),
+                U512::one()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_count_amounts_into_buckets() {
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(0)),
+                SeigniorageAllocation::validator(public_key(2), U512::from(9)),
+                SeigniorageAllocation::validator(public_key(3), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(1), U512::from(99)),
+                SeigniorageAllocation::delegator(public_key(5), public_key(1), U512::from(100)),
+                SeigniorageAllocation::delegator(public_key(6), public_key(2), U512::from(1_000)),
+            ],
+        };
+        let buckets = [U512::from(10), U512::from(100)];
+
+        assert_eq!(era_info.amount_histogram(&buckets), vec![2, 2, 2]);
+        assert_eq!(era_info.amount_histogram(&[]), vec![6]);
+        assert_eq!(EraInfo::new().amount_histogram(&buckets), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn should_filter_above_threshold_without_changing_original() {
+        let original = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(5)),
+                SeigniorageAllocation::validator(public_key(2), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(3), public_key(1), U512::from(15)),
+            ],
+        };
+        let snapshot = original.clone();
+
+        let mut filtered = original.above_threshold(U512::from(10));
+        assert_eq!(
+            filtered.seigniorage_allocations(),
+            &snapshot.seigniorage_allocations()[1..]
+        );
+
+        filtered.seigniorage_allocations_mut().clear();
+        assert!(filtered.is_empty());
+        assert_eq!(original, snapshot);
+    }
+
+    #[test]
+    fn should_stop_streaming_after_an_error() {
+        let mut bytes = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(1)),
+                SeigniorageAllocation::validator(public_key(2), U512::from(2)),
+            ],
+        }
+        .to_bytes()
+        .unwrap();
+        bytes.truncate(bytes.len() - 1);
+
+        let mut reader = SeigniorageAllocationReader::new(&bytes).unwrap();
+        assert!(matches!(reader.next(), Some(Ok(_))));
+        assert!(matches!(reader.next(), Some(Err(_))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn should_deduplicate_in_hash_set() {
+        use std::collections::HashSet;
+
+        let validator = SeigniorageAllocation::validator(public_key(1), U512::from(1));
+        let delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2));
+        let allocations: HashSet<SeigniorageAllocation> =
+            vec![validator.clone(), delegator.clone(), validator.clone()]
+                .into_iter()
+                .collect();
+        assert_eq!(allocations.len(), 2);
+
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![validator, delegator],
+        };
+        let era_infos: HashSet<EraInfo> = vec![era_info.clone(), EraInfo::new(), era_info]
+            .into_iter()
+            .collect();
+        assert_eq!(era_infos.len(), 2);
+    }
+
+    #[test]
+    fn should_merge_duplicate_recipients() {
+        let mut era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(5)),
+                SeigniorageAllocation::validator(public_key(1), U512::from(7)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(3), U512::from(1)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::max_value()),
+            ],
+        };
+
+        era_info.merge_duplicates();
+
+        assert_eq!(
+            era_info.seigniorage_allocations(),
+            &vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(17)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::max_value()),
+                SeigniorageAllocation::delegator(public_key(2), public_key(3), U512::from(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_aggregate_era_infos() {
+        let first = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(5)),
+            ],
+        };
+        let second = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(6)),
+                SeigniorageAllocation::validator(public_key(1), U512::from(20)),
+                SeigniorageAllocation::validator(public_key(3), U512::from(1)),
+            ],
+        };
+        let infos = vec![first, second];
+
+        let concatenated = EraInfo::aggregate(infos.clone(), EraInfoAggregation::Concat);
+        assert_eq!(concatenated.len(), 5);
+
+        let merged = EraInfo::aggregate(infos, EraInfoAggregation::Merge);
+        assert_eq!(
+            merged.seigniorage_allocations(),
+            &vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(30)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(11)),
+                SeigniorageAllocation::validator(public_key(3), U512::from(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_diff_against_previous_era_info() {
+        let previous = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(5)),
+                SeigniorageAllocation::delegator(public_key(3), public_key(1), U512::from(7)),
+            ],
+        };
+        let current = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(10)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(6)),
+                SeigniorageAllocation::delegator(public_key(4), public_key(1), U512::from(1)),
+            ],
+        };
+
+        let diff = current.diff(&previous);
+
+        assert_eq!(
+            diff.added(),
+            &[SeigniorageAllocation::delegator(
+                public_key(4),
+                public_key(1),
+                U512::from(1)
+            )]
+        );
+        assert_eq!(
+            diff.removed(),
+            &[SeigniorageAllocation::delegator(
+                public_key(3),
+                public_key(1),
+                U512::from(7)
+            )]
+        );
+        assert_eq!(
+            diff.changed(),
+            &[(
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(5)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(6)),
+            )]
+        );
+        assert!(current.diff(&current).is_empty());
+    }
+
+    #[test]
+    fn should_report_len_and_is_empty() {
+        let mut era_info = EraInfo::new();
+        assert!(era_info.is_empty());
+        assert_eq!(era_info.len(), 0);
+
+        era_info.seigniorage_allocations_mut().extend(vec![
+            SeigniorageAllocation::validator(public_key(1), U512::from(2)),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3)),
+        ]);
+        assert!(!era_info.is_empty());
+        assert_eq!(era_info.len(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_pretty_print_json() {
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(1)),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+            ],
+        };
+
+        let json = era_info.to_json_pretty().unwrap();
+        assert!(json.lines().count() > 1);
+        assert_eq!(serde_json::from_str::<EraInfo>(&json).unwrap(), era_info);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn should_flatten_allocations_to_json() {
+        use super::JsonSeigniorageAllocation;
+
+        let validator = SeigniorageAllocation::validator(public_key(1), U512::from(2));
+        let json = serde_json::to_value(JsonSeigniorageAllocation::from(&validator)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "Validator",
+                "validator": public_key(1),
+                "amount": "2",
+            })
+        );
+
+        let delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3));
+        let json = serde_json::to_value(JsonSeigniorageAllocation::from(&delegator)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "Delegator",
+                "validator": public_key(1),
+                "delegator": public_key(2),
+                "amount": "3",
+            })
+        );
+    }
+
+    #[test]
+    fn should_use_any_cl_type() {
+        assert_eq!(SeigniorageAllocation::cl_type(), CLType::Any);
+        assert_eq!(EraInfo::cl_type(), CLType::List(Box::new(CLType::Any)));
+    }
+
+    #[test]
+    fn should_report_allocation_variant() {
+        let validator = SeigniorageAllocation::validator(public_key(1), U512::from(2));
+        assert!(validator.is_validator());
+        assert!(!validator.is_delegator());
+
+        let delegator =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(3));
+        assert!(delegator.is_delegator());
+        assert!(!delegator.is_validator());
+    }
+
+    #[test]
+    fn saturating_total_should_saturate_on_overflow() {
+        let allocations = [
+            SeigniorageAllocation::validator(public_key(1), U512::max_value() - 1),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+            SeigniorageAllocation::delegator(public_key(3), public_key(1), U512::from(3)),
+        ];
+        assert_eq!(
+            SeigniorageAllocation::saturating_total(&allocations),
+            U512::max_value()
+        );
+        assert_eq!(
+            SeigniorageAllocation::saturating_total(&allocations[1..]),
+            U512::from(5)
+        );
+    }
+
+    #[test]
+    fn should_reject_length_prefix_over_bound() {
+        let bytes = u32::MAX.to_bytes().unwrap();
+        assert_eq!(
+            EraInfo::from_bytes_bounded(&bytes, 1_000),
+            Err(bytesrepr::Error::Formatting)
+        );
+
+        let era_info = EraInfo {
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::validator(public_key(1), U512::from(2)),
+                SeigniorageAllocation::validator(public_key(2), U512::from(3)),
+            ],
+        };
+        let bytes = era_info.to_bytes().unwrap();
+        assert_eq!(
+            EraInfo::from_bytes_bounded(&bytes, 1),
+            Err(bytesrepr::Error::Formatting)
+        );
     }
 }